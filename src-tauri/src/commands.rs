@@ -1,18 +1,33 @@
 // src-tauri/src/commands.rs
 
-use crate::config::{load_or_initialize_config, save_config};
-use crate::framework::fetch_framework;
-use crate::script::detect_package_manager_and_scripts;
-use crate::types::{FetchPackageJson, ProjectConfig, AppState};
+use crate::compose::{find_compose_file, list_services, ComposeAction, ComposeService};
+use crate::framework::{detect_frameworks, fetch_framework, framework_rules, FrameworkRule};
+use crate::graph::{build_graph, start_graph};
+use crate::history::{RunFilter, RunPage, ScriptStats};
+use crate::health::{check_project_health, health_from_package_json, locate_moved_project, read_git_remote, read_package_name};
+use crate::metrics::MetricsSample;
+use crate::node_version::{resolve_node, NodeResolution};
+use crate::package_json::PackageJsonCache;
+use crate::project_type::{detect_project_types, has_manifest};
+use crate::profiles::{
+    entry_readiness, find_running, profile_status, resolve_entry, EntryState, LaunchProfile, ProfileEntryStatus, ProfileMode, ProfileStatus,
+};
+use crate::queue::{dispatch, emit_queue, submit, submit_and_wait, ConcurrencyLimits, QueuedRun, Submission};
+use crate::readiness::wait_ready;
+use crate::runner::{request_restart, stop, LaunchRequest, RunTrigger};
+use crate::scheduler::{parse_cron, Schedule, ScheduleInfo};
+use crate::script::{detect_from_package_json, detect_package_manager_and_scripts, detect_tasks, find_task};
+use crate::store::AppStateSnapshot;
+use crate::toolchain::ToolchainReport;
+use crate::versions::detect_package_version;
+use crate::types::{FetchPackageJson, Project, ProjectConfig, ProjectEntry, ProjectHealth, ProjectStatus, ScriptRef, ScriptSettings, Task, AppState, script_settings_key};
 
-use serde_json::json;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::process::Stdio;
+use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager, Runtime};
 use tauri_plugin_dialog::{DialogExt, FilePath};
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command as TokioCommand;
-use log::{info, warn, error};
+use log::{error, info};
 
 
 /// Commande pour ajouter un projet.
@@ -20,11 +35,13 @@ use log::{info, warn, error};
 pub fn add_project<R: Runtime>(app_handle: AppHandle<R>) -> Result<(), String> {
     let main_window = app_handle.get_webview_window("main").ok_or("Fenêtre principale introuvable")?;
 
+    let dialog_handle = app_handle.clone();
     app_handle.dialog().file().pick_folder(move |selected_folder| {
+        let app_handle = dialog_handle;
         let project_path = match selected_folder.and_then(|folder| match folder {
-            FilePath::Path(path) if path.join("package.json").exists() => Some(path),
+            FilePath::Path(path) if has_manifest(&path) => Some(path),
             _ => {
-                let _ = main_window.emit("folder_error", "Dossier non valide ou package.json manquant.");
+                let _ = main_window.emit("folder_error", "Dossier non valide ou manifeste de projet manquant.");
                 None
             }
         }) {
@@ -33,9 +50,9 @@ pub fn add_project<R: Runtime>(app_handle: AppHandle<R>) -> Result<(), String> {
         };
 
         let project_path_str = project_path.to_string_lossy().to_string();
-        let mut config_data = load_or_initialize_config();
+        let state = app_handle.state::<AppState>();
 
-        if config_data.project_folders.iter().any(|project| project.path == project_path_str) {
+        if state.store.projects().iter().any(|project| project.path == project_path_str) {
             let _ = main_window.emit("folder_error", "Ce dossier est déjà ajouté.");
             return;
         }
@@ -46,16 +63,22 @@ pub fn add_project<R: Runtime>(app_handle: AppHandle<R>) -> Result<(), String> {
             path: project_path_str.clone(),
             name: project_path.file_name().unwrap().to_string_lossy().to_string(),
             framework: framework_data.as_ref().map(|f| f.name.clone()).unwrap_or_else(|| "Inconnu".to_string()),
-            framework_url: framework_data.map(|f| f.url),
+            framework_url: framework_data.as_ref().map(|f| f.url.clone()),
+            framework_version: framework_data.and_then(|f| f.version),
+            git_remote: read_git_remote(&project_path),
+            package_name: read_package_name(&project_path),
+            script_settings: HashMap::new(),
+            schedules: Vec::new(),
         };
-        config_data.project_folders.push(new_project.clone());
+        if let Err(e) = state.store.update(|config| config.project_folders.push(new_project.clone())) {
+            let _ = main_window.emit("folder_error", e);
+            return;
+        }
+        state.watcher.watch(&project_path);
+        println!("Projet avec ID {} ajouté.", new_project.id);
 
-        if let Err(e) = save_config(&config_data)
+        if let Err(e) = serde_json::to_string(&new_project)
             .map_err(|e| e.to_string())
-            .and_then(|_| {
-                println!("Projet avec ID {} ajouté.", new_project.id);
-                serde_json::to_string(&new_project).map_err(|e| e.to_string())
-            })
             .and_then(|project_json| main_window.emit("folder_success", project_json).map_err(|e| e.to_string()))
         {
             let _ = main_window.emit("folder_error", format!("Erreur : {}", e));
@@ -68,16 +91,15 @@ pub fn add_project<R: Runtime>(app_handle: AppHandle<R>) -> Result<(), String> {
 /// Commande pour supprimer un projet par son ID.
 #[tauri::command]
 pub async fn remove_project(state: tauri::State<'_, AppState>, id: String) -> Result<(), String> {
-    let mut config = load_or_initialize_config();
-    let initial_len = config.project_folders.len();
-    config.project_folders.retain(|project| project.id != id);
-
-    // Enregistrer les modifications et gérer les erreurs
-    save_config(&config).map_err(|e| format!("Erreur lors de la sauvegarde : {}", e))?;
+    let removed = state.store.update(|config| {
+        let removed = config.project_folders.iter().find(|project| project.id == id).cloned();
+        config.project_folders.retain(|project| project.id != id);
+        removed
+    })?;
 
-    if config.project_folders.len() < initial_len {
-        let mut projects = state.projects.lock().await;
-        projects.retain(|project| project.id != id);
+    if let Some(removed) = removed {
+        state.watcher.unwatch(&PathBuf::from(&removed.path));
+        state.watch_triggers.reload(&state.store.projects());
         println!("Projet avec ID {} supprimé.", id);
         Ok(())
     } else {
@@ -85,19 +107,255 @@ pub async fn remove_project(state: tauri::State<'_, AppState>, id: String) -> Re
     }
 }
 
+/// Commande pour récupérer les projets avec leur état de santé.
+/// Le remote git et le nom de paquet des projets enregistrés avant leur ajout sont complétés
+/// au passage, pour permettre de les retrouver s'ils sont déplacés.
 #[tauri::command]
-pub fn fetch_projects() -> Vec<ProjectConfig> {
-    let config = load_or_initialize_config();
-    config.project_folders
+pub fn fetch_projects(state: tauri::State<'_, AppState>) -> Vec<ProjectEntry> {
+    let entries: Vec<ProjectEntry> = state
+        .store
+        .projects()
+        .into_iter()
+        .map(|mut project| {
+            let path = PathBuf::from(&project.path);
+            let health = check_project_health(&path);
+            if health == ProjectHealth::Ok {
+                if project.git_remote.is_none() {
+                    project.git_remote = read_git_remote(&path);
+                }
+                if project.package_name.is_none() {
+                    project.package_name = read_package_name(&path);
+                }
+            }
+            ProjectEntry { config: project, health }
+        })
+        .collect();
+
+    let backfilled: Vec<&ProjectConfig> = entries
+        .iter()
+        .map(|entry| &entry.config)
+        .filter(|project| {
+            state.store.project(&project.id).is_some_and(|stored| {
+                stored.git_remote != project.git_remote || stored.package_name != project.package_name
+            })
+        })
+        .collect();
+    if !backfilled.is_empty() {
+        if let Err(e) = state.store.update(|config| {
+            for stored in config.project_folders.iter_mut() {
+                if let Some(project) = backfilled.iter().find(|project| project.id == stored.id) {
+                    stored.git_remote.clone_from(&project.git_remote);
+                    stored.package_name.clone_from(&project.package_name);
+                }
+            }
+        }) {
+            error!("Erreur lors de la mise à jour de la configuration : {}", e);
+        }
+    }
+    entries
+}
+
+/// Commande pour récupérer les projets entièrement renseignés (framework, scripts,
+/// gestionnaire de paquets) en un seul appel, calculés en parallèle.
+#[tauri::command]
+pub async fn fetch_projects_detailed(state: tauri::State<'_, AppState>) -> Result<Vec<Project>, String> {
+    let handles: Vec<_> = state
+        .store
+        .projects()
+        .into_iter()
+        .map(|project| {
+            let cache = Arc::clone(&state.package_cache);
+            let status = state.store.project_status(&project.id);
+            tauri::async_runtime::spawn_blocking(move || build_project(project, status, &cache))
+        })
+        .collect();
+
+    let mut projects = Vec::with_capacity(handles.len());
+    for handle in handles {
+        projects.push(handle.await.map_err(|e| e.to_string())?);
+    }
+    Ok(projects)
+}
+
+/// Construit un `Project` complet à partir de sa configuration.
+fn build_project(project: ProjectConfig, status: ProjectStatus, cache: &PackageJsonCache) -> Project {
+    let path = PathBuf::from(&project.path);
+    let package_json = cache.get(&path);
+    let health = health_from_package_json(&path, package_json.as_deref());
+
+    let frameworks = Some(detect_frameworks(&path, package_json.as_deref()));
+    let framework = frameworks.as_ref().and_then(|matches| matches.first());
+    let framework_version = framework
+        .and_then(|f| f.package.as_deref())
+        .zip(package_json.as_deref())
+        .map(|(package, package_json)| detect_package_version(&path, package_json, package))
+        .or(project.framework_version);
+    let package_info = package_json
+        .as_deref()
+        .map(|package_json| detect_from_package_json(&path, package_json));
+    let description = package_json
+        .as_deref()
+        .and_then(|package_json| package_json.get("description"))
+        .and_then(|d| d.as_str())
+        .map(str::to_string);
+
+    Project {
+        id: project.id,
+        name: project.name,
+        path: project.path,
+        framework: framework
+            .map(|f| f.name.clone())
+            .or(Some(project.framework)),
+        framework_url: framework.map(|f| f.url.clone()).or(project.framework_url),
+        description,
+        status: Some(status),
+        package_manager: package_info.as_ref().map(|info| info.manager.clone()),
+        scripts: package_info.map(|info| info.scripts),
+        health: Some(health),
+        frameworks,
+        framework_version,
+        project_types: Some(detect_project_types(&path)),
+        tasks: Some(detect_tasks(&path, package_json.as_deref())),
+    }
+}
+
+/// Commande pour relocaliser un projet déplacé ou renommé.
+/// Sans `new_path`, tente de retrouver le projet sous les racines connues
+/// (dossiers parents des projets enregistrés et dossier utilisateur).
+#[tauri::command]
+pub async fn relocate_project(
+    state: tauri::State<'_, AppState>,
+    id: String,
+    new_path: Option<String>,
+) -> Result<ProjectEntry, String> {
+    let projects = state.store.projects();
+    let project = projects
+        .iter()
+        .find(|project| project.id == id)
+        .cloned()
+        .ok_or_else(|| format!("Projet avec ID {} non trouvé.", id))?;
+    let old_path = project.path.clone();
+
+    let target_path = match new_path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let mut roots: Vec<PathBuf> = projects
+                .iter()
+                .filter_map(|p| PathBuf::from(&p.path).parent().map(|parent| parent.to_path_buf()))
+                .collect();
+            roots.extend(dirs::home_dir());
+            roots.dedup();
+
+            let name = project.name.clone();
+            tauri::async_runtime::spawn_blocking(move || locate_moved_project(&project, &roots))
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Impossible de retrouver le projet {}.", name))?
+        }
+    };
+
+    let health = check_project_health(&target_path);
+    if health != ProjectHealth::Ok {
+        return Err(format!(
+            "Le dossier {} n'est pas un projet valide ({:?}).",
+            target_path.display(),
+            health
+        ));
+    }
+
+    let target_path_str = target_path.to_string_lossy().to_string();
+    let git_remote = read_git_remote(&target_path);
+    let package_name = read_package_name(&target_path);
+
+    let updated = state.store.update(|config| {
+        if config
+            .project_folders
+            .iter()
+            .any(|p| p.id != id && p.path == target_path_str)
+        {
+            return Err("Ce dossier est déjà ajouté.".to_string());
+        }
+
+        let updated = config
+            .project_folders
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("Projet avec ID {} non trouvé.", id))?;
+        updated.path = target_path_str;
+        updated.git_remote = git_remote.or(updated.git_remote.take());
+        updated.package_name = package_name.or(updated.package_name.take());
+        Ok(updated.clone())
+    })??;
+    state.watcher.unwatch(&PathBuf::from(&old_path));
+    state.watcher.watch(&target_path);
+    state.watch_triggers.reload(&state.store.projects());
+    println!("Projet avec ID {} relocalisé vers {}.", id, updated.path);
+
+    Ok(ProjectEntry { config: updated, health })
+}
+
+/// Commande de débogage retournant un instantané de l'état en mémoire.
+#[tauri::command]
+pub fn app_state_snapshot(state: tauri::State<'_, AppState>) -> AppStateSnapshot {
+    state.store.snapshot()
+}
+
+/// Commande pour vérifier que le gestionnaire de paquets d'un projet est installé.
+#[tauri::command]
+pub async fn check_toolchain(
+    state: tauri::State<'_, AppState>,
+    project_id: String,
+) -> Result<ToolchainReport, String> {
+    let project = state
+        .store
+        .project(&project_id)
+        .ok_or_else(|| format!("Projet avec ID {} non trouvé.", project_id))?;
+    let path = PathBuf::from(&project.path);
+    let manager = state
+        .package_cache
+        .get(&path)
+        .map(|package_json| detect_from_package_json(&path, &package_json).manager)
+        .ok_or_else(|| format!("package.json introuvable ou invalide pour {}.", project.name))?;
+
+    let toolchain = Arc::clone(&state.toolchain);
+    tauri::async_runtime::spawn_blocking(move || toolchain.report(&manager))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Commande pour vérifier la version de Node exigée par un projet.
+#[tauri::command]
+pub async fn check_node_version(
+    state: tauri::State<'_, AppState>,
+    project_id: String,
+) -> Result<NodeResolution, String> {
+    let project = state
+        .store
+        .project(&project_id)
+        .ok_or_else(|| format!("Projet avec ID {} non trouvé.", project_id))?;
+
+    let toolchain = Arc::clone(&state.toolchain);
+    tauri::async_runtime::spawn_blocking(move || {
+        let default_node = toolchain.probe("node");
+        resolve_node(
+            &PathBuf::from(&project.path),
+            default_node.and_then(|node| node.version).as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Commande retournant les règles de détection des frameworks (intégrées et utilisateur).
+#[tauri::command]
+pub fn fetch_framework_rules() -> Vec<FrameworkRule> {
+    framework_rules().to_vec()
 }
 
 #[tauri::command]
 pub fn fetch_package_json(path: String) -> Option<FetchPackageJson> {
     let path = PathBuf::from(path);
-    detect_package_manager_and_scripts(&path).map(|info| FetchPackageJson {
-        manager: info.manager,
-        scripts: info.scripts,
-    })
+    detect_package_manager_and_scripts(&path)
 }
 
 #[tauri::command]
@@ -107,8 +365,8 @@ pub async fn run_script_project<R: Runtime>(
     command: String,
     path: String,
     id: String,
-) -> Result<(), String> {
-    // 1. Validation des entrées
+) -> Result<Submission, String> {
+    // Validation des entrées
     if manager.trim().is_empty() {
         return Err("Le gestionnaire de paquets ne peut pas être vide.".into());
     }
@@ -122,112 +380,533 @@ pub async fn run_script_project<R: Runtime>(
         return Err("L'ID du projet ne peut pas être vide.".into());
     }
 
-    // 2. Récupération de la fenêtre principale via get_webview_window
-    let window = app
-        .get_webview_window("main")
-        .ok_or_else(|| "La fenêtre principale n'a pas été trouvée".to_string())?;
+    // Lancement immédiat, ou mise en file d'attente si les limites de concurrence sont atteintes
+    submit(
+        app,
+        LaunchRequest {
+            project_id: id,
+            args: vec!["run".to_string(), command.clone()],
+            script: command,
+            source: Some("package.json".to_string()),
+            program: manager,
+            cwd: PathBuf::from(path),
+            env: HashMap::new(),
+            trigger: RunTrigger::Manual,
+        },
+    )
+    .await
+}
 
-    // 3. Lancement de la tâche asynchrone pour exécuter la commande
-    tokio::spawn(async move {
-        info!("Exécution du script '{}' pour le projet ID '{}'", command, id);
+/// Commande pour lister les tâches exécutables d'un projet, toutes sources confondues.
+#[tauri::command]
+pub async fn fetch_tasks(state: tauri::State<'_, AppState>, project_id: String) -> Result<Vec<Task>, String> {
+    let project = state
+        .store
+        .project(&project_id)
+        .ok_or_else(|| format!("Projet avec ID {} non trouvé.", project_id))?;
 
-        // 4. Tentative de lancement de la commande
-        let mut cmd = match TokioCommand::new(&manager)
-            .arg("run")
-            .arg(&command)
-            .current_dir(&path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-        {
-            Ok(cmd) => cmd,
-            Err(e) => {
-                error!("Erreur lors du lancement du script: {}", e);
-                let payload = json!({
-                    "id": id,
-                    "output": format!("Erreur lors de l'exécution du script: {}", e),
-                });
-                let _ = window.emit("script_error", payload);
-                return;
-            }
+    let cache = state.package_cache.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = PathBuf::from(&project.path);
+        detect_tasks(&path, cache.get(&path).as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Commande pour exécuter une tâche d'un projet, identifiée par sa source et son nom.
+#[tauri::command]
+pub async fn run_task<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    project_id: String,
+    source: String,
+    name: String,
+) -> Result<Submission, String> {
+    let project = app
+        .state::<AppState>()
+        .store
+        .project(&project_id)
+        .ok_or_else(|| format!("Projet avec ID {} non trouvé.", project_id))?;
+    let path = PathBuf::from(&project.path);
+
+    let task = find_task(&path, Some(&source), &name)
+        .ok_or_else(|| format!("Tâche {} ({}) introuvable pour {}.", name, source, project.name))?;
+
+    submit(app, LaunchRequest::for_task(&project_id, task, path)).await
+}
+
+/// Commande pour lister les services Docker Compose d'un projet avec leur état.
+#[tauri::command]
+pub async fn fetch_compose_services(
+    state: tauri::State<'_, AppState>,
+    project_id: String,
+) -> Result<Vec<ComposeService>, String> {
+    let project = state
+        .store
+        .project(&project_id)
+        .ok_or_else(|| format!("Projet avec ID {} non trouvé.", project_id))?;
+    let Some(compose_file) = find_compose_file(&PathBuf::from(&project.path)) else {
+        return Ok(Vec::new());
+    };
+
+    let toolchain = Arc::clone(&state.toolchain);
+    tauri::async_runtime::spawn_blocking(move || {
+        let docker = toolchain.probe("docker").map(|tool| PathBuf::from(tool.path));
+        list_services(docker.as_deref(), &compose_file)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Commande pour démarrer, arrêter, redémarrer ou suivre les logs d'un service Docker Compose.
+/// La sortie est relayée par les mêmes événements que les scripts ; l'action est mise en file
+/// d'attente si les limites de concurrence sont atteintes.
+#[tauri::command]
+pub async fn compose_service_action<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    project_id: String,
+    service: String,
+    action: ComposeAction,
+) -> Result<Submission, String> {
+    let project = app
+        .state::<AppState>()
+        .store
+        .project(&project_id)
+        .ok_or_else(|| format!("Projet avec ID {} non trouvé.", project_id))?;
+    let path = PathBuf::from(&project.path);
+    if find_compose_file(&path).is_none() {
+        return Err(format!("Aucun fichier Docker Compose trouvé pour {}.", project.name));
+    }
+
+    submit(
+        app,
+        LaunchRequest {
+            project_id,
+            script: format!("compose:{}", service),
+            source: None,
+            program: "docker".to_string(),
+            args: action.args(&service),
+            cwd: path,
+            env: HashMap::new(),
+            trigger: RunTrigger::Manual,
+        },
+    )
+    .await
+}
+
+/// Commande pour arrêter un script en cours d'exécution.
+#[tauri::command]
+pub fn stop_script<R: Runtime>(app: tauri::AppHandle<R>, run_id: String) -> Result<(), String> {
+    stop(&app, &run_id)
+}
+
+/// Commande pour redémarrer un script en cours d'exécution.
+#[tauri::command]
+pub fn restart_script<R: Runtime>(app: tauri::AppHandle<R>, run_id: String) -> Result<(), String> {
+    request_restart(&app, &run_id)
+}
+
+/// Commande pour récupérer les dernières mesures CPU et mémoire d'un script en cours.
+#[tauri::command]
+pub fn get_script_metrics(state: tauri::State<'_, AppState>, run_id: String) -> Result<Vec<MetricsSample>, String> {
+    if state.store.process(&run_id).is_none() {
+        return Err(format!("Exécution {} introuvable.", run_id));
+    }
+    Ok(state.metrics.get(&run_id).unwrap_or_default())
+}
+
+/// Commande pour lister les lancements en attente d'une place libre.
+#[tauri::command]
+pub fn fetch_run_queue(state: tauri::State<'_, AppState>) -> Vec<QueuedRun> {
+    state.run_queue.entries()
+}
+
+/// Commande pour déplacer un lancement dans la file d'attente.
+#[tauri::command]
+pub fn reorder_queued_run<R: Runtime>(app: tauri::AppHandle<R>, queue_id: String, position: usize) -> Result<(), String> {
+    app.state::<AppState>().run_queue.reorder(&queue_id, position)?;
+    emit_queue(&app);
+    Ok(())
+}
+
+/// Commande pour annuler un lancement en attente.
+#[tauri::command]
+pub fn cancel_queued_run<R: Runtime>(app: tauri::AppHandle<R>, queue_id: String) -> Result<(), String> {
+    let cancelled = app
+        .state::<AppState>()
+        .run_queue
+        .cancel(&queue_id)
+        .ok_or_else(|| format!("Lancement {} introuvable dans la file.", queue_id))?;
+    info!("Lancement du script '{}' annulé pour le projet ID '{}'", cancelled.script, cancelled.project_id);
+    emit_queue(&app);
+    Ok(())
+}
+
+/// Commande pour récupérer les limites de scripts exécutés simultanément.
+#[tauri::command]
+pub fn fetch_concurrency_limits(state: tauri::State<'_, AppState>) -> ConcurrencyLimits {
+    state.store.concurrency()
+}
+
+/// Commande pour modifier les limites de concurrence ; les lancements en attente
+/// démarrent aussitôt si les nouvelles limites le permettent.
+#[tauri::command]
+pub async fn update_concurrency_limits<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    limits: ConcurrencyLimits,
+) -> Result<(), String> {
+    app.state::<AppState>().store.update(|config| config.concurrency = limits)?;
+    dispatch(&app).await;
+    Ok(())
+}
+
+/// Commande pour parcourir l'historique des exécutions, de la plus récente à la plus ancienne.
+#[tauri::command]
+pub async fn list_runs(state: tauri::State<'_, AppState>, filter: Option<RunFilter>) -> Result<RunPage, String> {
+    Ok(state.history.list(&filter.unwrap_or_default()))
+}
+
+/// Commande pour calculer les statistiques d'exécution des scripts (durée médiane, taux de réussite).
+#[tauri::command]
+pub async fn fetch_run_stats(
+    state: tauri::State<'_, AppState>,
+    project_id: Option<String>,
+) -> Result<Vec<ScriptStats>, String> {
+    Ok(state.history.stats(project_id.as_deref()))
+}
+
+/// Commande pour lister les lancements planifiés, éventuellement d'un seul projet, avec leur prochaine échéance.
+#[tauri::command]
+pub fn schedule_list(state: tauri::State<'_, AppState>, project_id: Option<String>) -> Vec<ScheduleInfo> {
+    crate::scheduler::list(&state.store.projects(), project_id.as_deref())
+}
+
+/// Commande pour créer ou remplacer un lancement planifié.
+#[tauri::command]
+pub fn schedule_save(
+    state: tauri::State<'_, AppState>,
+    project_id: String,
+    mut schedule: Schedule,
+) -> Result<Schedule, String> {
+    parse_cron(&schedule.cron)?;
+    if schedule.id.trim().is_empty() {
+        schedule.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    let found = state.store.update(|config| {
+        let Some(project) = config.project_folders.iter_mut().find(|p| p.id == project_id) else {
+            return false;
         };
+        match project.schedules.iter_mut().find(|s| s.id == schedule.id) {
+            Some(existing) => *existing = schedule.clone(),
+            None => project.schedules.push(schedule.clone()),
+        }
+        true
+    })?;
+    if !found {
+        return Err(format!("Projet avec ID {} non trouvé.", project_id));
+    }
+    info!("Planification {} enregistrée.", schedule.cron);
+    Ok(schedule)
+}
 
-        // 5. Gestion de la sortie standard (stdout)
-        if let Some(stdout) = cmd.stdout.take() {
-            let window_clone = window.clone();
-            let id_clone = id.clone();
-            tokio::spawn(async move {
-                let reader = BufReader::new(stdout);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    let payload = json!({
-                        "id": id_clone,
-                        "output": line,
-                    });
-                    info!("Emitting script_output: {:?}", payload);
-                    if let Err(e) = window_clone.emit("script_output", payload) {
-                        error!("Erreur lors de l'émission de script_output: {}", e);
-                    }
-                }
-            });
+/// Commande pour supprimer un lancement planifié.
+#[tauri::command]
+pub fn schedule_delete(state: tauri::State<'_, AppState>, project_id: String, schedule_id: String) -> Result<(), String> {
+    let removed = state.store.update(|config| {
+        config
+            .project_folders
+            .iter_mut()
+            .find(|p| p.id == project_id)
+            .map(|project| {
+                let count = project.schedules.len();
+                project.schedules.retain(|s| s.id != schedule_id);
+                count != project.schedules.len()
+            })
+            .unwrap_or(false)
+    })?;
+
+    if removed {
+        Ok(())
+    } else {
+        Err(format!("Planification avec ID {} non trouvée.", schedule_id))
+    }
+}
+
+/// Commande pour activer un lancement planifié.
+#[tauri::command]
+pub fn schedule_enable(state: tauri::State<'_, AppState>, project_id: String, schedule_id: String) -> Result<(), String> {
+    set_schedule_enabled(&state, &project_id, &schedule_id, true)
+}
+
+/// Commande pour désactiver un lancement planifié.
+#[tauri::command]
+pub fn schedule_disable(state: tauri::State<'_, AppState>, project_id: String, schedule_id: String) -> Result<(), String> {
+    set_schedule_enabled(&state, &project_id, &schedule_id, false)
+}
+
+fn set_schedule_enabled(state: &AppState, project_id: &str, schedule_id: &str, enabled: bool) -> Result<(), String> {
+    state.store.update(|config| {
+        config
+            .project_folders
+            .iter_mut()
+            .filter(|p| p.id == project_id)
+            .flat_map(|p| p.schedules.iter_mut())
+            .find(|s| s.id == schedule_id)
+            .map(|schedule| schedule.enabled = enabled)
+            .ok_or_else(|| format!("Planification avec ID {} non trouvée.", schedule_id))
+    })?
+}
+
+/// Commande pour récupérer les profils de lancement.
+#[tauri::command]
+pub fn fetch_profiles(state: tauri::State<'_, AppState>) -> Vec<LaunchProfile> {
+    state.store.profiles()
+}
+
+/// Commande pour créer ou remplacer un profil de lancement.
+#[tauri::command]
+pub fn save_profile(state: tauri::State<'_, AppState>, mut profile: LaunchProfile) -> Result<LaunchProfile, String> {
+    if profile.id.trim().is_empty() {
+        profile.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    state.store.update(|config| {
+        match config.profiles.iter_mut().find(|p| p.id == profile.id) {
+            Some(existing) => *existing = profile.clone(),
+            None => config.profiles.push(profile.clone()),
         }
+    })?;
+    info!("Profil {} enregistré.", profile.name);
+    Ok(profile)
+}
 
-        // 6. Gestion des erreurs standard (stderr)
-        if let Some(stderr) = cmd.stderr.take() {
-            let window_clone = window.clone();
-            let id_clone = id.clone();
-            tokio::spawn(async move {
-                let reader = BufReader::new(stderr);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    let payload = json!({
-                        "id": id_clone,
-                        "output": line,
-                    });
-                    warn!("Emitting script_error: {:?}", payload);
-                    if let Err(e) = window_clone.emit("script_error", payload) {
-                        error!("Erreur lors de l'émission de script_error: {}", e);
-                    }
-                }
-            });
+/// Commande pour supprimer un profil de lancement.
+#[tauri::command]
+pub fn delete_profile(state: tauri::State<'_, AppState>, id: String) -> Result<(), String> {
+    let removed = state.store.update(|config| {
+        let count = config.profiles.len();
+        config.profiles.retain(|profile| profile.id != id);
+        count != config.profiles.len()
+    })?;
+
+    if removed {
+        info!("Profil avec ID {} supprimé.", id);
+        Ok(())
+    } else {
+        Err(format!("Profil avec ID {} non trouvé.", id))
+    }
+}
+
+/// Commande pour lancer tous les scripts d'un profil, en parallèle ou dans l'ordre.
+/// Les scripts déjà en cours ne sont pas relancés.
+#[tauri::command]
+pub async fn start_profile<R: Runtime>(app: tauri::AppHandle<R>, id: String) -> Result<ProfileStatus, String> {
+    let state = app.state::<AppState>();
+    let profile = state
+        .store
+        .profile(&id)
+        .ok_or_else(|| format!("Profil avec ID {} non trouvé.", id))?;
+    let processes = state.store.processes();
+
+    let mut pending = Vec::new();
+    let mut statuses: Vec<Option<ProfileEntryStatus>> = Vec::with_capacity(profile.entries.len());
+    for (index, entry) in profile.entries.iter().enumerate() {
+        if let Some(process) = find_running(&processes, entry) {
+            statuses.push(Some(ProfileEntryStatus::new(entry, EntryState::Running, Some(process.run_id.clone()), None)));
+            continue;
         }
 
-        // 7. Attente de la fin du processus et gestion du résultat
-        match cmd.wait().await {
-            Ok(status) => {
-                if status.success() {
-                    info!("Script '{}' terminé avec succès pour le projet ID '{}'", command, id);
-                    let _ = window.emit("script_finished", json!({"id": id}));
-                } else {
-                    warn!(
-                        "Script '{}' terminé avec un code d'erreur {} pour le projet ID '{}'",
-                        command,
-                        status.code().unwrap_or(-1),
-                        id
-                    );
-                    let payload = json!({
-                        "id": id,
-                        "output": format!("Le script a terminé avec un code d'erreur {}", status.code().unwrap_or(-1)),
-                    });
-                    let _ = window.emit("script_error", payload);
-                }
+        let request = state
+            .store
+            .project(&entry.project_id)
+            .ok_or_else(|| format!("Projet avec ID {} non trouvé.", entry.project_id))
+            .and_then(|project| resolve_entry(&project, entry));
+        match request {
+            Ok(request) => {
+                statuses.push(None);
+                pending.push((index, request));
             }
-            Err(e) => {
-                error!("Erreur lors de l'attente du script: {}", e);
-                let payload = json!({
-                    "id": id,
-                    "output": format!("Erreur lors de l'attente du script: {}", e),
-                });
-                let _ = window.emit("script_error", payload);
+            Err(e) => statuses.push(Some(ProfileEntryStatus::new(entry, EntryState::Failed, None, Some(e)))),
+        }
+    }
+
+    // Les scripts sont soumis à la file d'attente : en mode parallèle, ceux qui dépassent les limites
+    // de concurrence restent en attente ; en mode séquentiel, chacun attend d'avoir démarré.
+    let results: Vec<(usize, Result<Submission, String>)> = match profile.mode {
+        ProfileMode::Parallel => {
+            let handles: Vec<_> = pending
+                .into_iter()
+                .map(|(index, request)| {
+                    let app = app.clone();
+                    (index, tauri::async_runtime::spawn(async move { submit(app, request).await }))
+                })
+                .collect();
+
+            let mut results = Vec::with_capacity(handles.len());
+            for (index, handle) in handles {
+                results.push((index, handle.await.map_err(|e| e.to_string()).and_then(|result| result)));
             }
+            results
         }
-    });
+        ProfileMode::Sequential => {
+            let mut results: Vec<(usize, Result<Submission, String>)> = Vec::with_capacity(pending.len());
+            for (index, request) in pending {
+                let previous_failed = statuses[..index]
+                    .iter()
+                    .flatten()
+                    .any(|status| status.state == EntryState::Failed)
+                    || results.iter().any(|(_, result)| result.is_err());
+                if previous_failed {
+                    let entry = &profile.entries[index];
+                    statuses[index] = Some(ProfileEntryStatus::new(entry, EntryState::Skipped, None, None));
+                    continue;
+                }
+                // Le script suivant n'est lancé qu'une fois celui-ci prêt, en s'abonnant aux
+                // événements avant le lancement pour ne manquer aucune ligne de sortie.
+                let readiness = (index + 1 < profile.entries.len())
+                    .then(|| state.store.project(&request.project_id))
+                    .flatten()
+                    .and_then(|project| entry_readiness(&project, &profile.entries[index]));
+                let mut events = state.run_events.subscribe();
+                let run_id = match submit_and_wait(app.clone(), request).await {
+                    Ok(run_id) => run_id,
+                    Err(e) => {
+                        results.push((index, Err(e)));
+                        continue;
+                    }
+                };
+                if let Some((check, timeout)) = readiness {
+                    if let Err(e) = wait_ready(&check, &run_id, &mut events, timeout).await {
+                        let entry = &profile.entries[index];
+                        statuses[index] = Some(ProfileEntryStatus::new(entry, EntryState::Failed, Some(run_id), Some(e)));
+                        continue;
+                    }
+                }
+                results.push((index, Ok(Submission::Started { run_id })));
+            }
+            results
+        }
+    };
 
-    Ok(())
+    for (index, result) in results {
+        let entry = &profile.entries[index];
+        statuses[index] = Some(match result {
+            Ok(Submission::Started { run_id }) => ProfileEntryStatus::new(entry, EntryState::Running, Some(run_id), None),
+            Ok(Submission::Queued { .. }) => ProfileEntryStatus::new(entry, EntryState::Queued, None, None),
+            Err(e) => ProfileEntryStatus::new(entry, EntryState::Failed, None, Some(e)),
+        });
+    }
+
+    info!("Profil {} démarré.", profile.name);
+    Ok(ProfileStatus::new(&profile.id, statuses.into_iter().flatten().collect()))
+}
+
+/// Commande pour arrêter tous les scripts en cours d'un profil.
+#[tauri::command]
+pub fn stop_profile<R: Runtime>(app: tauri::AppHandle<R>, id: String) -> Result<ProfileStatus, String> {
+    let state = app.state::<AppState>();
+    let profile = state
+        .store
+        .profile(&id)
+        .ok_or_else(|| format!("Profil avec ID {} non trouvé.", id))?;
+    let processes = state.store.processes();
+
+    let entries = profile
+        .entries
+        .iter()
+        .map(|entry| match find_running(&processes, entry) {
+            Some(process) => match stop(&app, &process.run_id) {
+                Ok(()) => ProfileEntryStatus::new(entry, EntryState::Stopped, Some(process.run_id.clone()), None),
+                Err(e) => ProfileEntryStatus::new(entry, EntryState::Failed, Some(process.run_id.clone()), Some(e)),
+            },
+            None => ProfileEntryStatus::new(entry, EntryState::Stopped, None, None),
+        })
+        .collect();
+
+    info!("Profil {} arrêté.", profile.name);
+    Ok(ProfileStatus::new(&profile.id, entries))
 }
 
+/// Commande pour récupérer l'état agrégé d'un profil.
+#[tauri::command]
+pub fn get_profile_status(state: tauri::State<'_, AppState>, id: String) -> Result<ProfileStatus, String> {
+    let profile = state
+        .store
+        .profile(&id)
+        .ok_or_else(|| format!("Profil avec ID {} non trouvé.", id))?;
+    Ok(profile_status(&profile, &state.store.processes()))
+}
+
+/// Commande pour modifier les réglages d'exécution d'un script (dépendances, disponibilité,
+/// redémarrage, sonde de santé, limites, déclencheur sur changement de fichiers).
+#[tauri::command]
+pub fn update_script_settings(
+    state: tauri::State<'_, AppState>,
+    project_id: String,
+    script: String,
+    source: Option<String>,
+    settings: ScriptSettings,
+) -> Result<(), String> {
+    let key = script_settings_key(source.as_deref(), &script);
+    let projects = state.store.projects();
+    let mut updated = projects.clone();
+    let project = updated
+        .iter_mut()
+        .find(|project| project.id == project_id)
+        .ok_or_else(|| format!("Projet avec ID {} non trouvé.", project_id))?;
+    project.script_settings.insert(key.clone(), settings.clone());
+
+    // Refuse les réglages qui introduiraient un cycle de dépendances.
+    build_graph(
+        &updated,
+        &ScriptRef {
+            project_id: Some(project_id.clone()),
+            script,
+            source,
+        },
+    )?;
 
+    state.store.update(|config| {
+        if let Some(project) = config.project_folders.iter_mut().find(|p| p.id == project_id) {
+            project.script_settings.insert(key, settings);
+        }
+    })?;
+    state.watch_triggers.reload(&state.store.projects());
+    Ok(())
+}
 
+/// Commande pour lancer un script et ses prérequis dans l'ordre topologique.
+/// Retourne l'identifiant du graphe et les scripts dans leur ordre de démarrage ;
+/// l'avancement est signalé par les événements `script_graph_status`.
+#[tauri::command]
+pub fn start_script_graph<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    project_id: String,
+    script: String,
+    source: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let projects = app.state::<AppState>().store.projects();
+    let nodes = build_graph(
+        &projects,
+        &ScriptRef {
+            project_id: Some(project_id),
+            script,
+            source,
+        },
+    )?;
 
+    let graph_id = uuid::Uuid::new_v4().to_string();
+    let order: Vec<ScriptRef> = nodes
+        .iter()
+        .map(|node| ScriptRef {
+            project_id: Some(node.project_id.clone()),
+            script: node.script.clone(),
+            source: node.source.clone(),
+        })
+        .collect();
+    start_graph(app, graph_id.clone(), nodes);
 
+    Ok(serde_json::json!({ "graph_id": graph_id, "order": order }))
+}
 
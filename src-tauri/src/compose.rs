@@ -0,0 +1,297 @@
+// src-tauri/src/compose.rs
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_yaml::Value as Yaml;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Fichiers Docker Compose reconnus, par ordre de priorité (identique à `docker compose`).
+const COMPOSE_FILES: &[&str] = &["compose.yaml", "compose.yml", "docker-compose.yaml", "docker-compose.yml"];
+
+/// État d'un service Docker Compose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceState {
+    Running,
+    Restarting,
+    Paused,
+    Exited,
+    Created,
+    NotCreated,
+    Unknown,
+}
+
+/// Service déclaré dans le fichier Compose d'un projet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposeService {
+    pub name: String,
+    pub image: Option<String>,
+    pub ports: Vec<String>,
+    pub depends_on: Vec<String>,
+    pub state: ServiceState,
+    pub health: Option<String>,
+}
+
+/// Action applicable à un service Docker Compose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComposeAction {
+    Up,
+    Down,
+    Restart,
+    Logs,
+}
+
+impl ComposeAction {
+    /// Arguments de `docker compose` correspondant à l'action pour un service. Le fichier Compose
+    /// n'est pas précisé : lancé depuis le dossier du projet, `docker compose` le retrouve avec
+    /// ses éventuels fichiers d'override (`compose.override.yaml`, ...).
+    pub fn args(self, service: &str) -> Vec<String> {
+        let action_args: &[&str] = match self {
+            ComposeAction::Up => &["up", "--detach"],
+            ComposeAction::Down => &["rm", "--stop", "--force"],
+            ComposeAction::Restart => &["restart"],
+            ComposeAction::Logs => &["logs", "--follow", "--tail", "200"],
+        };
+
+        std::iter::once("compose")
+            .chain(action_args.iter().copied())
+            .map(str::to_string)
+            .chain(std::iter::once(service.to_string()))
+            .collect()
+    }
+}
+
+/// Retourne le fichier Compose d'un projet, s'il en a un.
+pub fn find_compose_file(project_dir: &Path) -> Option<PathBuf> {
+    COMPOSE_FILES
+        .iter()
+        .map(|file_name| project_dir.join(file_name))
+        .find(|path| path.is_file())
+}
+
+/// Lit les services déclarés dans un fichier Compose, sans leur état.
+pub fn read_services(compose_file: &Path) -> Vec<ComposeService> {
+    let Some(document) = fs::read_to_string(compose_file)
+        .ok()
+        .and_then(|contents| serde_yaml::from_str::<Yaml>(&contents).ok())
+    else {
+        return Vec::new();
+    };
+    let Some(services) = document.get("services").and_then(Yaml::as_mapping) else {
+        return Vec::new();
+    };
+
+    services
+        .iter()
+        .filter_map(|(name, definition)| {
+            Some(ComposeService {
+                name: name.as_str()?.to_string(),
+                image: definition.get("image").and_then(Yaml::as_str).map(str::to_string),
+                ports: definition
+                    .get("ports")
+                    .and_then(Yaml::as_sequence)
+                    .map(|ports| ports.iter().filter_map(port_mapping).collect())
+                    .unwrap_or_default(),
+                depends_on: match definition.get("depends_on") {
+                    Some(Yaml::Sequence(services)) => services
+                        .iter()
+                        .filter_map(Yaml::as_str)
+                        .map(str::to_string)
+                        .collect(),
+                    Some(Yaml::Mapping(services)) => services
+                        .keys()
+                        .filter_map(Yaml::as_str)
+                        .map(str::to_string)
+                        .collect(),
+                    _ => Vec::new(),
+                },
+                state: ServiceState::Unknown,
+                health: None,
+            })
+        })
+        .collect()
+}
+
+/// Normalise un port Compose (syntaxe courte `"5432:5432"` ou longue `{published, target}`).
+fn port_mapping(port: &Yaml) -> Option<String> {
+    match port {
+        Yaml::String(port) => Some(port.clone()),
+        Yaml::Number(port) => Some(port.to_string()),
+        Yaml::Mapping(_) => {
+            let field = |key: &str| match port.get(key)? {
+                Yaml::String(value) => Some(value.clone()),
+                Yaml::Number(value) => Some(value.to_string()),
+                _ => None,
+            };
+            let target = field("target")?;
+            Some(match field("published") {
+                Some(published) => format!("{}:{}", published, target),
+                None => target,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Lit les services d'un projet et renseigne leur état via `docker compose ps`, lancé depuis
+/// le dossier du fichier Compose pour tenir compte des fichiers d'override.
+/// Sans exécutable docker, les services gardent l'état `Unknown`.
+pub fn list_services(docker: Option<&Path>, compose_file: &Path) -> Vec<ComposeService> {
+    let mut services = read_services(compose_file);
+    let Some(docker) = docker else {
+        return services;
+    };
+
+    let Some(output) = Command::new(docker)
+        .args(["compose", "ps", "--all", "--format", "json"])
+        .current_dir(compose_file.parent().unwrap_or(Path::new(".")))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+    else {
+        return services;
+    };
+
+    let containers = parse_ps_output(&String::from_utf8_lossy(&output.stdout));
+    for service in services.iter_mut() {
+        match containers
+            .iter()
+            .find(|container| container.get("Service").and_then(Value::as_str) == Some(service.name.as_str()))
+        {
+            Some(container) => {
+                service.state = container
+                    .get("State")
+                    .and_then(Value::as_str)
+                    .map(parse_state)
+                    .unwrap_or(ServiceState::Unknown);
+                service.health = container
+                    .get("Health")
+                    .and_then(Value::as_str)
+                    .filter(|health| !health.is_empty())
+                    .map(str::to_string);
+            }
+            None => service.state = ServiceState::NotCreated,
+        }
+    }
+    services
+}
+
+/// Analyse la sortie de `docker compose ps --format json` : un tableau JSON
+/// (anciennes versions) ou un objet JSON par ligne (versions récentes).
+fn parse_ps_output(output: &str) -> Vec<Value> {
+    match serde_json::from_str::<Value>(output.trim()) {
+        Ok(Value::Array(containers)) => containers,
+        Ok(container @ Value::Object(_)) => vec![container],
+        _ => output
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .collect(),
+    }
+}
+
+fn parse_state(state: &str) -> ServiceState {
+    match state {
+        "running" => ServiceState::Running,
+        "restarting" => ServiceState::Restarting,
+        "paused" => ServiceState::Paused,
+        "exited" | "dead" => ServiceState::Exited,
+        "created" => ServiceState::Created,
+        _ => ServiceState::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPOSE: &str = "services:\n  db:\n    image: postgres:16\n    ports:\n      - \"5432:5432\"\n  web:\n    image: nginx\n    depends_on:\n      db:\n        condition: service_healthy\n  worker:\n    image: busybox\n";
+
+    #[test]
+    fn action_args_do_not_pin_the_compose_file() {
+        assert_eq!(ComposeAction::Up.args("db"), ["compose", "up", "--detach", "db"]);
+        assert_eq!(ComposeAction::Down.args("db"), ["compose", "rm", "--stop", "--force", "db"]);
+        assert!(!ComposeAction::Logs.args("db").iter().any(|arg| arg == "--file"));
+    }
+
+    #[test]
+    fn parses_ps_output_formats() {
+        let array = r#"[{"Service":"db","State":"running"},{"Service":"web","State":"exited"}]"#;
+        assert_eq!(parse_ps_output(array).len(), 2);
+        let lines = "{\"Service\":\"db\",\"State\":\"running\"}\n{\"Service\":\"web\",\"State\":\"exited\"}\n";
+        assert_eq!(parse_ps_output(lines).len(), 2);
+        assert_eq!(parse_ps_output(r#"{"Service":"db"}"#).len(), 1);
+        assert!(parse_ps_output("").is_empty());
+    }
+
+    /// Le faux `docker` enregistre son dossier courant et ses arguments, puis imite `docker compose ps`.
+    #[cfg(unix)]
+    #[test]
+    fn lists_services_with_fake_docker() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let project = tempfile::tempdir().unwrap();
+        let compose_file = project.path().join("compose.yaml");
+        fs::write(&compose_file, COMPOSE).unwrap();
+        fs::write(project.path().join("compose.override.yaml"), "services: {}\n").unwrap();
+
+        let bin = tempfile::tempdir().unwrap();
+        let log = bin.path().join("calls.log");
+        let docker = bin.path().join("docker");
+        fs::write(
+            &docker,
+            format!(
+                "#!/bin/sh\npwd > '{log}'\necho \"$@\" >> '{log}'\n\
+                 echo '{{\"Service\":\"db\",\"State\":\"running\",\"Health\":\"healthy\"}}'\n\
+                 echo '{{\"Service\":\"web\",\"State\":\"exited\",\"Health\":\"\"}}'\n",
+                log = log.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&docker, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let services = list_services(Some(&docker), &compose_file);
+        let state = |name: &str| {
+            let service = services.iter().find(|service| service.name == name).unwrap();
+            (service.state, service.health.clone())
+        };
+        assert_eq!(state("db"), (ServiceState::Running, Some("healthy".to_string())));
+        assert_eq!(state("web"), (ServiceState::Exited, None));
+        assert_eq!(state("worker"), (ServiceState::NotCreated, None));
+
+        let db = services.iter().find(|service| service.name == "db").unwrap();
+        assert_eq!(db.ports, ["5432:5432"]);
+        let web = services.iter().find(|service| service.name == "web").unwrap();
+        assert_eq!(web.depends_on, ["db"]);
+
+        let calls = fs::read_to_string(&log).unwrap();
+        let mut lines = calls.lines();
+        assert_eq!(
+            Path::new(lines.next().unwrap()).canonicalize().unwrap(),
+            project.path().canonicalize().unwrap()
+        );
+        assert_eq!(lines.next(), Some("compose ps --all --format json"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keeps_unknown_state_when_docker_fails() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let project = tempfile::tempdir().unwrap();
+        let compose_file = project.path().join("docker-compose.yml");
+        fs::write(&compose_file, COMPOSE).unwrap();
+        assert_eq!(find_compose_file(project.path()), Some(compose_file.clone()));
+
+        let bin = tempfile::tempdir().unwrap();
+        let docker = bin.path().join("docker");
+        fs::write(&docker, "#!/bin/sh\nexit 1\n").unwrap();
+        fs::set_permissions(&docker, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let services = list_services(Some(&docker), &compose_file);
+        assert_eq!(services.len(), 3);
+        assert!(services.iter().all(|service| service.state == ServiceState::Unknown));
+    }
+}
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
-use std::fs::{File, OpenOptions};
+use std::fs::{self, File, OpenOptions};
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
-use std::io::Error;
+use std::io::{Error, ErrorKind};
+use crate::profiles::LaunchProfile;
+use crate::queue::ConcurrencyLimits;
 use crate::types::ProjectConfig;
 
 const CONFIG_FILE_NAME: &str = ".dld-config.json";
@@ -10,6 +12,10 @@ const CONFIG_FILE_NAME: &str = ".dld-config.json";
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DevLauncherConfig {
     pub project_folders: Vec<ProjectConfig>,
+    #[serde(default)]
+    pub profiles: Vec<LaunchProfile>,
+    #[serde(default)]
+    pub concurrency: ConcurrencyLimits,
 }
 
 /// Obtient le chemin du fichier de configuration.
@@ -35,29 +41,71 @@ pub fn load_config() -> Result<DevLauncherConfig, Error> {
     let mut contents = String::new();
     reader.read_to_string(&mut contents)?;
     
-    let config: DevLauncherConfig = serde_json::from_str(&contents).unwrap_or_else(|_| DevLauncherConfig {
-        project_folders: Vec::new(),
-    });
+    let config: DevLauncherConfig = serde_json::from_str(&contents)?;
     Ok(config)
 }
 
+/// Met de côté un fichier de configuration illisible avant qu'il ne soit remplacé.
+fn backup_config() -> Result<PathBuf, Error> {
+    let backup_path = get_config_file_path().with_file_name(format!(
+        "{}.{}.bak",
+        CONFIG_FILE_NAME,
+        chrono::Local::now().format("%Y%m%d%H%M%S")
+    ));
+    fs::copy(get_config_file_path(), &backup_path)?;
+    Ok(backup_path)
+}
+
 /// Initialise une configuration par défaut et la sauvegarde dans le fichier de configuration.
 pub fn initialize_config() -> Result<DevLauncherConfig, Error> {
     let default_config = DevLauncherConfig {
         project_folders: Vec::new(),
+        profiles: Vec::new(),
+        concurrency: ConcurrencyLimits::default(),
     };
     save_config(&default_config)?;
     Ok(default_config)
 }
 
 /// Charge ou initialise le fichier de configuration.
-pub fn load_or_initialize_config() -> DevLauncherConfig {
-    load_config().unwrap_or_else(|_| initialize_config().unwrap_or_else(|e| {
-        eprintln!("Erreur lors de l'initialisation du fichier de configuration: {:?}", e);
-        DevLauncherConfig {
-            project_folders: Vec::new(),
+/// Un fichier illisible est copié à côté de l'original avant d'être remplacé par une configuration vide ;
+/// si cette copie échoue, l'erreur est retournée avec la configuration vide, qui ne doit pas être sauvegardée.
+pub fn load_or_initialize_config() -> (DevLauncherConfig, Option<String>) {
+    let empty_config = || DevLauncherConfig {
+        project_folders: Vec::new(),
+        profiles: Vec::new(),
+        concurrency: ConcurrencyLimits::default(),
+    };
+
+    match load_config() {
+        Ok(config) => (config, None),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            let config = initialize_config().unwrap_or_else(|e| {
+                eprintln!("Erreur lors de l'initialisation du fichier de configuration: {:?}", e);
+                empty_config()
+            });
+            (config, None)
         }
-    }))
+        Err(e) => {
+            eprintln!("Erreur lors de la lecture du fichier de configuration: {}", e);
+            match backup_config() {
+                Ok(backup_path) => {
+                    eprintln!("Configuration illisible copiée dans {}", backup_path.display());
+                    (empty_config(), None)
+                }
+                Err(backup_error) => (
+                    empty_config(),
+                    Some(format!(
+                        "Le fichier de configuration {} est illisible ({}) et n'a pas pu être copié ({}) : \
+                         les modifications ne seront pas enregistrées.",
+                        get_config_file_path().display(),
+                        e,
+                        backup_error
+                    )),
+                ),
+            }
+        }
+    }
 }
 
 /// Sauvegarde les paramètres de configuration dans le fichier de configuration.
@@ -1,106 +1,416 @@
 // src-tauri/src/framework.rs
 
+use crate::package_json::read_package_json;
+use crate::project_type::{detect_project_types, read_dependencies, ManifestDependencies, ProjectType};
+use crate::versions::{detect_package_version, PackageVersion};
+use log::warn;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
 
 pub struct FrameworkInfo {
     pub name: String,
     pub url: String,
+    pub version: Option<PackageVersion>,
 }
 
-const FRAMEWORKS: &[(&str, &str, &str)] = &[
-    // ("clé de dépendance", "Nom du framework", "URL du site officiel")
-    ("next", "Next.js", "https://nextjs.org/"),
-    ("nuxt", "Nuxt.js", "https://nuxtjs.org/"),
-    ("gatsby", "Gatsby", "https://www.gatsbyjs.com/"),
-    ("remix", "Remix", "https://remix.run/"),
-    ("sapper", "Sapper", "https://sapper.svelte.dev/"),
-    ("blitz", "Blitz.js", "https://blitzjs.com/"),
-    ("gridsome", "Gridsome", "https://gridsome.org/"),
-    ("sveltekit", "SvelteKit", "https://kit.svelte.dev/"),
-    ("quasar", "Quasar", "https://quasar.dev/"),
-    ("ember", "Ember.js", "https://emberjs.com/"),
-    ("angular", "Angular", "https://angular.io/"),
-    ("vue", "Vue.js", "https://vuejs.org/"),
-    ("react-native", "React Native", "https://reactnative.dev/"),
-    ("ionic", "Ionic", "https://ionicframework.com/"),
-    ("stencil", "Stencil", "https://stenciljs.com/"),
-    ("meteor", "Meteor", "https://www.meteor.com/"),
-    ("electron", "Electron", "https://www.electronjs.org/"),
-    ("expo", "Expo", "https://expo.dev/"),
-    ("nativescript", "NativeScript", "https://nativescript.org/"),
-    ("tauri", "Tauri", "https://tauri.app/"),
-    ("capacitor", "Capacitor", "https://capacitorjs.com/"),
-    ("laravel", "Laravel", "https://laravel.com/"),
-    // Frameworks généraux
-    ("react", "React", "https://reactjs.org/"),
-    ("preact", "Preact", "https://preactjs.com/"),
-    ("svelte", "Svelte", "https://svelte.dev/"),
-    ("solid", "SolidJS", "https://www.solidjs.com/"),
-    ("alpine", "Alpine.js", "https://alpinejs.dev/"),
-    ("mithril", "Mithril", "https://mithril.js.org/"),
-    ("backbone", "Backbone.js", "https://backbonejs.org/"),
-    ("aurelia", "Aurelia", "https://aurelia.io/"),
-    // Outils de bundling/build
-    ("webpack", "Webpack", "https://webpack.js.org/"),
-    ("vite", "Vite", "https://vitejs.dev/"),
-    ("parcel", "Parcel", "https://parceljs.org/"),
-    ("gulp", "Gulp", "https://gulpjs.com/"),
-    ("grunt", "Grunt", "https://gruntjs.com/"),
-    // Autres frameworks ou outils
-    ("express", "Express", "https://expressjs.com/"),
-    ("koa", "Koa", "https://koajs.com/"),
-    ("hapi", "Hapi", "https://hapi.dev/"),
-    ("sails", "Sails.js", "https://sailsjs.com/"),
-    ("nest", "NestJS", "https://nestjs.com/"),
-    ("adonis", "AdonisJS", "https://adonisjs.com/"),
-    ("loopback", "LoopBack", "https://loopback.io/"),
-    ("fastify", "Fastify", "https://www.fastify.io/"),
-    ("strapi", "Strapi", "https://strapi.io/"),
-    ("keystone", "KeystoneJS", "https://keystonejs.com/"),
-];
-
-pub fn fetch_framework(path: &PathBuf) -> Option<FrameworkInfo> {
-    let package_json_path = path.join("package.json");
-
-    if !package_json_path.exists() {
-        return None;
-    }
-
-    let file = File::open(&package_json_path).ok()?;
-    let reader = BufReader::new(file);
-    let package_json: Value = serde_json::from_reader(reader).ok()?;
-
-    let dependencies = package_json.get("dependencies").and_then(|d| d.as_object());
-    let dev_dependencies = package_json
-        .get("devDependencies")
-        .and_then(|d| d.as_object());
-
-    // Fusionner les dépendances et les dépendances de développement
-    let mut all_dependencies = serde_json::Map::new();
-    if let Some(deps) = dependencies {
-        all_dependencies.extend(deps.clone());
-    }
-    if let Some(dev_deps) = dev_dependencies {
-        all_dependencies.extend(dev_deps.clone());
-    }
-
-    // Parcourir la liste des frameworks pour trouver une correspondance
-    for (key, framework_name, framework_url) in FRAMEWORKS {
-        if all_dependencies.contains_key(*key)
-            || all_dependencies
-                .keys()
-                .any(|k| k.starts_with(&format!("@{}/", key)))
-            || all_dependencies.contains_key(&format!("@{}", key))
-        {
-            return Some(FrameworkInfo {
-                name: framework_name.to_string(),
-                url: framework_url.to_string(),
-            });
+/// Rôle d'un framework dans un projet, du plus générique au plus spécifique. Le bundler passe
+/// avant la bibliothèque UI : c'est lui qui sert l'application en développement (Vite + React).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameworkRole {
+    #[default]
+    Tooling,
+    UiLibrary,
+    Bundler,
+    Server,
+    Platform,
+    MetaFramework,
+}
+
+/// Framework détecté dans un projet avec son indice de confiance (entre 0 et 1).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameworkMatch {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub role: FrameworkRole,
+    pub confidence: f32,
+    pub icon: Option<String>,
+    pub package: Option<String>,
+    pub config_file: Option<String>,
+}
+
+/// Confiance attribuée à une dépendance de production.
+const DEPENDENCY_CONFIDENCE: f32 = 1.0;
+/// Confiance attribuée à une dépendance de développement.
+const DEV_DEPENDENCY_CONFIDENCE: f32 = 0.8;
+/// Confiance attribuée à une correspondance sur un préfixe de scope uniquement.
+const SCOPED_PREFIX_CONFIDENCE: f32 = 0.5;
+/// Confiance attribuée à un fichier de configuration sans dépendance directe correspondante.
+const CONFIG_FILE_CONFIDENCE: f32 = 0.9;
+
+/// Règles de détection intégrées à l'application.
+const BUILTIN_FRAMEWORKS: &str = include_str!("frameworks.json");
+
+/// Fichier utilisateur permettant d'ajouter ou de remplacer des règles (par `id`).
+const USER_FRAMEWORKS_FILE_NAME: &str = ".dld-frameworks.json";
+
+/// Règle de détection d'un framework.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameworkRule {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub project_type: ProjectType,
+    #[serde(default)]
+    pub role: FrameworkRole,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub scoped_prefixes: Vec<String>,
+    #[serde(default)]
+    pub config_files: Vec<String>,
+    #[serde(default)]
+    pub default_port: Option<u16>,
+    #[serde(default)]
+    pub ready_pattern: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+impl FrameworkRule {
+    /// Calcule la confiance de la règle pour les dépendances du projet et retourne
+    /// la dépendance correspondante, ou `None` sans correspondance.
+    fn confidence(&self, manifest: &ManifestDependencies) -> Option<(f32, String)> {
+        let (dependencies, dev_dependencies) = (&manifest.dependencies, &manifest.dev_dependencies);
+        let matches_key = |keys: &[String]| self.dependencies.iter().find(|key| keys.contains(key)).cloned();
+        let matches_prefix = |keys: &[String]| {
+            keys.iter()
+                .find(|dependency| {
+                    self.scoped_prefixes
+                        .iter()
+                        .any(|prefix| dependency.starts_with(prefix.as_str()))
+                })
+                .cloned()
+        };
+
+        if let Some(package) = matches_key(dependencies) {
+            Some((DEPENDENCY_CONFIDENCE, package))
+        } else if let Some(package) = matches_key(dev_dependencies) {
+            Some((DEV_DEPENDENCY_CONFIDENCE, package))
+        } else {
+            matches_prefix(dependencies)
+                .or_else(|| matches_prefix(dev_dependencies))
+                .map(|package| (SCOPED_PREFIX_CONFIDENCE, package))
+        }
+    }
+
+    /// Retourne le premier fichier de configuration caractéristique présent dans le projet.
+    /// Un marqueur terminé par `.*` accepte n'importe quelle extension (`next.config.*`).
+    fn find_config_file(&self, project_dir: &Path, root_files: &[String]) -> Option<String> {
+        self.config_files.iter().find_map(|marker| match marker.strip_suffix(".*") {
+            Some(stem) => root_files
+                .iter()
+                .find(|file| {
+                    file.strip_prefix(stem)
+                        .is_some_and(|extension| extension.starts_with('.'))
+                })
+                .cloned(),
+            None => project_dir.join(marker).is_file().then(|| marker.clone()),
+        })
+    }
+
+    fn to_match(&self, confidence: f32, package: Option<String>, config_file: Option<String>) -> FrameworkMatch {
+        FrameworkMatch {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            url: self.url.clone(),
+            role: self.role,
+            confidence,
+            icon: self.icon.clone(),
+            package,
+            config_file,
         }
     }
+}
+
+/// Retourne les règles de détection, chargées une seule fois.
+pub fn framework_rules() -> &'static [FrameworkRule] {
+    static RULES: OnceLock<Vec<FrameworkRule>> = OnceLock::new();
+    RULES.get_or_init(load_framework_rules)
+}
+
+/// Charge les règles intégrées puis applique les règles du fichier utilisateur :
+/// une règle existante est remplacée, une nouvelle règle est prioritaire sur les règles intégrées.
+fn load_framework_rules() -> Vec<FrameworkRule> {
+    let mut rules: Vec<FrameworkRule> =
+        serde_json::from_str(BUILTIN_FRAMEWORKS).expect("frameworks.json intégré invalide");
+
+    let Some(user_file_path) = dirs::home_dir().map(|home| home.join(USER_FRAMEWORKS_FILE_NAME)) else {
+        return rules;
+    };
+    let Ok(contents) = fs::read_to_string(&user_file_path) else {
+        return rules;
+    };
+
+    let user_rules: Vec<FrameworkRule> = match serde_json::from_str(&contents) {
+        Ok(user_rules) => user_rules,
+        Err(e) => {
+            warn!("Fichier {} invalide : {}", user_file_path.display(), e);
+            return rules;
+        }
+    };
+
+    let mut new_rules = Vec::new();
+    for user_rule in user_rules {
+        match rules.iter_mut().find(|rule| rule.id == user_rule.id) {
+            Some(rule) => *rule = user_rule,
+            None => new_rules.push(user_rule),
+        }
+    }
+    new_rules.extend(rules);
+    new_rules
+}
+
+pub fn fetch_framework(path: &Path) -> Option<FrameworkInfo> {
+    let package_json = read_package_json(path);
+    let primary = detect_frameworks(path, package_json.as_ref()).into_iter().next()?;
+    let version = primary
+        .package
+        .as_deref()
+        .zip(package_json.as_ref())
+        .map(|(package, package_json)| detect_package_version(path, package_json, package));
+
+    Some(FrameworkInfo {
+        name: primary.name,
+        url: primary.url,
+        version,
+    })
+}
+
+/// Retourne le motif de sortie signalant qu'un serveur de développement du projet est prêt,
+/// d'après le framework détecté le plus spécifique qui en définit un.
+pub fn detect_ready_pattern(project_dir: &Path) -> Option<String> {
+    let package_json = read_package_json(project_dir);
+    detect_frameworks(project_dir, package_json.as_ref())
+        .iter()
+        .find_map(|detected| {
+            framework_rules()
+                .iter()
+                .find(|rule| rule.id == detected.id)?
+                .ready_pattern
+                .clone()
+        })
+}
+
+/// Détecte tous les frameworks d'un projet à partir des dépendances de ses manifestes et de ses fichiers
+/// de configuration, triés du plus spécifique au plus générique : par rôle (méta-framework,
+/// plateforme, serveur, bundler, bibliothèque UI), puis par confiance.
+/// Un fichier de configuration confirme une dépendance, ou suffit à détecter un framework
+/// qui n'est qu'une dépendance transitive.
+pub fn detect_frameworks(project_dir: &Path, package_json: Option<&Value>) -> Vec<FrameworkMatch> {
+    let mut project_types = detect_project_types(project_dir);
+    if package_json.is_some() && !project_types.contains(&ProjectType::Node) {
+        project_types.push(ProjectType::Node);
+    }
+    let manifests: HashMap<ProjectType, ManifestDependencies> = project_types
+        .into_iter()
+        .map(|project_type| (project_type, read_dependencies(project_dir, project_type, package_json)))
+        .collect();
+    let no_dependencies = ManifestDependencies::default();
+
+    let root_files: Vec<String> = fs::read_dir(project_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
 
-    None
-}
\ No newline at end of file
+    let mut matches: Vec<FrameworkMatch> = framework_rules()
+        .iter()
+        .filter_map(|rule| {
+            let manifest = manifests.get(&rule.project_type).unwrap_or(&no_dependencies);
+            let dependency_match = rule.confidence(manifest);
+            let config_file = rule.find_config_file(project_dir, &root_files);
+
+            let confidence = match (&dependency_match, &config_file) {
+                (Some(_), Some(_)) => DEPENDENCY_CONFIDENCE,
+                (Some((confidence, _)), None) => *confidence,
+                (None, Some(_)) => CONFIG_FILE_CONFIDENCE,
+                (None, None) => return None,
+            };
+            let package = dependency_match.map(|(_, package)| package);
+            Some(rule.to_match(confidence, package, config_file))
+        })
+        .collect();
+
+    // Les correspondances sur un simple préfixe de scope passent après les dépendances explicites.
+    // Tri stable : les règles de même rôle et de même confiance gardent l'ordre du fichier.
+    let is_explicit = |m: &FrameworkMatch| m.confidence > SCOPED_PREFIX_CONFIDENCE;
+    matches.sort_by(|a, b| {
+        is_explicit(b)
+            .cmp(&is_explicit(a))
+            .then(b.role.cmp(&a.role))
+            .then(b.confidence.total_cmp(&a.confidence))
+    });
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Détecte les frameworks d'un projet construit à partir d'un package.json et de fichiers vides.
+    fn detect(package_json: &str, files: &[&str]) -> Vec<(String, FrameworkRole, f32)> {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("package.json"), package_json).unwrap();
+        for file in files {
+            fs::write(dir.path().join(file), "").unwrap();
+        }
+        let package_json = read_package_json(dir.path());
+        detect_frameworks(dir.path(), package_json.as_ref())
+            .into_iter()
+            .map(|m| (m.id, m.role, m.confidence))
+            .collect()
+    }
+
+    fn ids(matches: &[(String, FrameworkRole, f32)]) -> Vec<&str> {
+        matches.iter().map(|(id, _, _)| id.as_str()).collect()
+    }
+
+    #[test]
+    fn role_ordering() {
+        use FrameworkRole::*;
+        assert!(MetaFramework > Platform && Platform > Server && Server > Bundler);
+        assert!(Bundler > UiLibrary && UiLibrary > Tooling);
+    }
+
+    #[test]
+    fn vite_react_prefers_the_bundler() {
+        let matches = detect(
+            r#"{ "dependencies": { "react": "^18.3.1", "react-dom": "^18.3.1" }, "devDependencies": { "vite": "^5.4.0" } }"#,
+            &["vite.config.ts"],
+        );
+        assert_eq!(ids(&matches), ["vite", "react"]);
+        // Le fichier de configuration confirme la dépendance de développement.
+        assert_eq!(matches[0], ("vite".to_string(), FrameworkRole::Bundler, DEPENDENCY_CONFIDENCE));
+        assert_eq!(matches[1], ("react".to_string(), FrameworkRole::UiLibrary, DEPENDENCY_CONFIDENCE));
+
+        // Sans fichier de configuration, la dépendance de développement suffit.
+        let matches = detect(
+            r#"{ "dependencies": { "react": "^18.3.1" }, "devDependencies": { "vite": "^5.4.0" } }"#,
+            &[],
+        );
+        assert_eq!(matches[0], ("vite".to_string(), FrameworkRole::Bundler, DEV_DEPENDENCY_CONFIDENCE));
+    }
+
+    #[test]
+    fn meta_framework_comes_before_bundler() {
+        let matches = detect(
+            r#"{ "dependencies": { "next": "14.2.3", "react": "^18" }, "devDependencies": { "webpack": "^5.91.0" } }"#,
+            &[],
+        );
+        assert_eq!(ids(&matches), ["next", "webpack", "react"]);
+        let roles: Vec<FrameworkRole> = matches.iter().map(|(_, role, _)| *role).collect();
+        assert_eq!(roles, [FrameworkRole::MetaFramework, FrameworkRole::Bundler, FrameworkRole::UiLibrary]);
+    }
+
+    #[test]
+    fn angular_and_vue_are_told_apart() {
+        let matches = detect(
+            r#"{ "dependencies": { "@angular/core": "^17.3.0", "@angular/router": "^17.3.0", "vue": "^3.4.0" } }"#,
+            &[],
+        );
+        assert_eq!(ids(&matches), ["angular", "vue"]);
+        assert_eq!(matches[0], ("angular".to_string(), FrameworkRole::UiLibrary, DEPENDENCY_CONFIDENCE));
+        assert_eq!(matches[1], ("vue".to_string(), FrameworkRole::UiLibrary, DEPENDENCY_CONFIDENCE));
+
+        // Un paquet `@angular/*` isolé ne fait pas d'un projet Vue un projet Angular.
+        let matches = detect(r#"{ "dependencies": { "@angular/animations": "^17.3.0", "vue": "^3.4.0" } }"#, &[]);
+        assert_eq!(ids(&matches), ["vue", "angular"]);
+        assert_eq!(matches[1], ("angular".to_string(), FrameworkRole::UiLibrary, SCOPED_PREFIX_CONFIDENCE));
+    }
+
+    #[test]
+    fn solid_from_solid_js_only() {
+        let matches = detect(r#"{ "dependencies": { "solid-js": "^1.8.0" } }"#, &[]);
+        assert_eq!(matches, [("solid".to_string(), FrameworkRole::UiLibrary, DEPENDENCY_CONFIDENCE)]);
+
+        // `@solid/*` n'appartient pas à SolidJS, contrairement à `@solidjs/*`.
+        assert!(detect(r#"{ "dependencies": { "@solid/community-server": "^7.0.0" } }"#, &[]).is_empty());
+        let matches = detect(r#"{ "dependencies": { "@solidjs/router": "^0.13.0" } }"#, &[]);
+        assert_eq!(matches, [("solid".to_string(), FrameworkRole::UiLibrary, SCOPED_PREFIX_CONFIDENCE)]);
+    }
+
+    #[test]
+    fn next_comes_before_react() {
+        let matches = detect(
+            r#"{ "dependencies": { "next": "14.2.3", "react": "^18", "react-dom": "^18" } }"#,
+            &["next.config.mjs"],
+        );
+        assert_eq!(ids(&matches), ["next", "react"]);
+        assert_eq!(matches[0], ("next".to_string(), FrameworkRole::MetaFramework, DEPENDENCY_CONFIDENCE));
+    }
+
+    #[test]
+    fn nuxt_from_config_file_and_dev_dependency() {
+        let matches = detect(
+            r#"{ "devDependencies": { "nuxt": "^3.12.0" }, "dependencies": { "vue": "^3.4.0" } }"#,
+            &[],
+        );
+        assert_eq!(ids(&matches), ["nuxt", "vue"]);
+        assert_eq!(matches[0], ("nuxt".to_string(), FrameworkRole::MetaFramework, DEV_DEPENDENCY_CONFIDENCE));
+        assert_eq!(matches[1], ("vue".to_string(), FrameworkRole::UiLibrary, DEPENDENCY_CONFIDENCE));
+
+        let matches = detect(r#"{ "dependencies": { "vue": "^3.4.0" } }"#, &["nuxt.config.ts"]);
+        assert_eq!(matches[0], ("nuxt".to_string(), FrameworkRole::MetaFramework, CONFIG_FILE_CONFIDENCE));
+    }
+
+    #[test]
+    fn astro_integrations_rank_after_astro() {
+        let matches = detect(
+            r#"{ "dependencies": { "astro": "^4.11.0", "@astrojs/react": "^3.6.0", "react": "^18" } }"#,
+            &["astro.config.mjs"],
+        );
+        assert_eq!(ids(&matches), ["astro", "react"]);
+        assert_eq!(matches[0], ("astro".to_string(), FrameworkRole::MetaFramework, DEPENDENCY_CONFIDENCE));
+    }
+
+    #[test]
+    fn express_only() {
+        let matches = detect(r#"{ "dependencies": { "express": "^4.19.2" } }"#, &[]);
+        assert_eq!(matches, [("express".to_string(), FrameworkRole::Server, DEPENDENCY_CONFIDENCE)]);
+    }
+
+    #[test]
+    fn scoped_prefix_only_ranks_last() {
+        let matches = detect(
+            r#"{ "dependencies": { "@nestjs/common": "^10.0.0", "express": "^4.19.2" } }"#,
+            &[],
+        );
+        assert_eq!(ids(&matches), ["express", "nest"]);
+        assert_eq!(matches[1], ("nest".to_string(), FrameworkRole::Server, SCOPED_PREFIX_CONFIDENCE));
+    }
+
+    #[test]
+    fn svelte_config_alone_is_not_sveltekit() {
+        let matches = detect(r#"{ "devDependencies": { "svelte": "^4.2.0", "vite": "^5.4.0" } }"#, &["svelte.config.js"]);
+        assert_eq!(ids(&matches), ["vite", "svelte"]);
+        assert_eq!(matches[1], ("svelte".to_string(), FrameworkRole::UiLibrary, DEPENDENCY_CONFIDENCE));
+
+        let matches = detect(
+            r#"{ "devDependencies": { "@sveltejs/kit": "^2.5.0", "svelte": "^4.2.0" } }"#,
+            &["svelte.config.js"],
+        );
+        assert_eq!(ids(&matches), ["sveltekit", "svelte"]);
+        assert_eq!(matches[0], ("sveltekit".to_string(), FrameworkRole::MetaFramework, DEV_DEPENDENCY_CONFIDENCE));
+    }
+}
@@ -0,0 +1,370 @@
+// src-tauri/src/graph.rs
+
+use crate::framework::detect_ready_pattern;
+use crate::readiness::{wait_ready, Readiness, ReadinessCheck, DEFAULT_READINESS_TIMEOUT};
+use crate::queue::submit_and_wait;
+use crate::runner::{LaunchRequest, RunEvent, RunTrigger};
+use crate::script::find_task;
+use crate::types::{script_settings_key, split_script_settings_key, AppState, ProjectConfig, ScriptRef, ScriptSettings};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::watch;
+
+/// Script d'un graphe de dépendances, avec les indices de ses prérequis.
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub project_id: String,
+    pub script: String,
+    pub source: Option<String>,
+    pub settings: ScriptSettings,
+    pub depends_on: Vec<usize>,
+    pub has_dependents: bool,
+}
+
+/// État d'un script au sein d'un graphe en cours de démarrage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeState {
+    Waiting,
+    Starting,
+    Ready,
+    Completed,
+    Exited,
+    Failed,
+}
+
+impl NodeState {
+    fn is_settled(self) -> bool {
+        !matches!(self, NodeState::Waiting | NodeState::Starting)
+    }
+
+    fn is_satisfied(self) -> bool {
+        matches!(self, NodeState::Ready | NodeState::Completed)
+    }
+}
+
+/// Construit le graphe de dépendances d'un script, trié dans l'ordre topologique
+/// (prérequis d'abord). Retourne une erreur décrivant le cycle s'il en existe un.
+pub fn build_graph(projects: &[ProjectConfig], root: &ScriptRef) -> Result<Vec<GraphNode>, String> {
+    let mut nodes = Vec::new();
+    let mut index_of = HashMap::new();
+    let mut stack = Vec::new();
+    visit(projects, root, &mut nodes, &mut index_of, &mut stack)?;
+
+    for index in 0..nodes.len() {
+        for dependency in nodes[index].depends_on.clone() {
+            nodes[dependency].has_dependents = true;
+        }
+    }
+    Ok(nodes)
+}
+
+/// Parcours en profondeur : les prérequis sont ajoutés avant le script qui en dépend.
+fn visit(
+    projects: &[ProjectConfig],
+    script: &ScriptRef,
+    nodes: &mut Vec<GraphNode>,
+    index_of: &mut HashMap<(String, String), usize>,
+    stack: &mut Vec<(String, String)>,
+) -> Result<usize, String> {
+    let project_id = script
+        .project_id
+        .clone()
+        .ok_or_else(|| format!("Projet non précisé pour le script {}.", script.script))?;
+    let key = (project_id.clone(), script_settings_key(script.source.as_deref(), &script.script));
+
+    if let Some(position) = stack.iter().position(|visiting| *visiting == key) {
+        let cycle: Vec<String> = stack[position..]
+            .iter()
+            .chain(std::iter::once(&key))
+            .map(|(project_id, key)| {
+                let (_, script) = split_script_settings_key(key);
+                format!("{}:{}", project_name(projects, project_id), script)
+            })
+            .collect();
+        return Err(format!("Cycle de dépendances : {}", cycle.join(" -> ")));
+    }
+    if let Some(index) = index_of.get(&key) {
+        return Ok(*index);
+    }
+
+    let project = projects
+        .iter()
+        .find(|project| project.id == project_id)
+        .ok_or_else(|| format!("Projet avec ID {} non trouvé.", project_id))?;
+    let settings = project
+        .settings_for(script.source.as_deref(), &script.script)
+        .cloned()
+        .unwrap_or_default();
+
+    stack.push(key.clone());
+    let mut depends_on = Vec::with_capacity(settings.depends_on.len());
+    for dependency in &settings.depends_on {
+        // Sans projet précisé, la dépendance désigne un script du même projet.
+        let dependency = ScriptRef {
+            project_id: dependency.project_id.clone().or_else(|| Some(project_id.clone())),
+            script: dependency.script.clone(),
+            source: dependency.source.clone(),
+        };
+        depends_on.push(visit(projects, &dependency, nodes, index_of, stack)?);
+    }
+    stack.pop();
+
+    nodes.push(GraphNode {
+        project_id,
+        script: script.script.clone(),
+        source: script.source.clone(),
+        settings,
+        depends_on,
+        has_dependents: false,
+    });
+    index_of.insert(key, nodes.len() - 1);
+    Ok(nodes.len() - 1)
+}
+
+fn project_name<'a>(projects: &'a [ProjectConfig], id: &'a str) -> &'a str {
+    projects
+        .iter()
+        .find(|project| project.id == id)
+        .map(|project| project.name.as_str())
+        .unwrap_or(id)
+}
+
+/// Démarre les scripts d'un graphe : chacun attend que tous ses prérequis soient prêts,
+/// et échoue sans être lancé si l'un d'eux échoue ou se termine avant.
+pub fn start_graph<R: Runtime>(app: AppHandle<R>, graph_id: String, nodes: Vec<GraphNode>) {
+    let (senders, receivers): (Vec<_>, Vec<_>) = nodes
+        .iter()
+        .map(|_| watch::channel(NodeState::Waiting))
+        .unzip();
+
+    for (index, node) in nodes.into_iter().enumerate() {
+        let app = app.clone();
+        let graph_id = graph_id.clone();
+        let state_tx = senders[index].clone();
+        let prerequisites: Vec<watch::Receiver<NodeState>> = node
+            .depends_on
+            .iter()
+            .map(|dependency| receivers[*dependency].clone())
+            .collect();
+
+        tokio::spawn(async move {
+            run_node(app, graph_id, node, state_tx, prerequisites).await;
+        });
+    }
+}
+
+/// Attend les prérequis d'un script, le lance puis attend sa disponibilité.
+async fn run_node<R: Runtime>(
+    app: AppHandle<R>,
+    graph_id: String,
+    node: GraphNode,
+    state_tx: watch::Sender<NodeState>,
+    mut prerequisites: Vec<watch::Receiver<NodeState>>,
+) {
+    let report = |node_state: NodeState, run_id: Option<&str>, error: Option<&str>| {
+        state_tx.send_replace(node_state);
+        let payload = json!({
+            "graph_id": graph_id,
+            "project_id": node.project_id,
+            "script": node.script,
+            "state": node_state,
+            "run_id": run_id,
+            "error": error,
+        });
+        if let Err(e) = app.emit("script_graph_status", payload) {
+            error!("Erreur lors de l'émission de script_graph_status: {}", e);
+        }
+    };
+
+    // 1. Attente des prérequis
+    for prerequisite in prerequisites.iter_mut() {
+        if prerequisite.wait_for(|state| state.is_settled()).await.is_err() {
+            report(NodeState::Failed, None, Some("Prérequis interrompu."));
+            return;
+        }
+    }
+    // Un prérequis prêt a pu se terminer entre-temps : tous doivent l'être simultanément.
+    if prerequisites.iter().any(|prerequisite| !prerequisite.borrow().is_satisfied()) {
+        warn!("Prérequis indisponible pour le script '{}' du projet ID '{}'", node.script, node.project_id);
+        report(NodeState::Failed, None, Some("Un prérequis a échoué ou s'est arrêté."));
+        return;
+    }
+
+    let state = app.state::<AppState>();
+
+    // 2. Un script déjà lancé en dehors du graphe est considéré comme prêt.
+    if let Some(process) = state
+        .store
+        .processes()
+        .into_iter()
+        .find(|process| process.runs(&node.project_id, node.source.as_deref(), &node.script))
+    {
+        report(NodeState::Ready, Some(&process.run_id), None);
+        return;
+    }
+
+    let Some(project) = state.store.project(&node.project_id) else {
+        report(NodeState::Failed, None, Some("Projet introuvable."));
+        return;
+    };
+    let path = PathBuf::from(&project.path);
+    let Some(task) = find_task(&path, node.source.as_deref(), &node.script) else {
+        report(NodeState::Failed, None, Some("Tâche introuvable."));
+        return;
+    };
+
+    // 3. Lancement, éventuellement après une attente dans la file si les limites de concurrence
+    // sont atteintes, en s'abonnant aux événements avant pour ne manquer aucune ligne de sortie.
+    report(NodeState::Starting, None, None);
+    let mut events = state.run_events.subscribe();
+    let request = LaunchRequest {
+        trigger: RunTrigger::Graph,
+        ..LaunchRequest::for_task(&node.project_id, task, path.clone())
+    };
+    let run_id = match submit_and_wait(app.clone(), request).await {
+        Ok(run_id) => run_id,
+        Err(e) => {
+            report(NodeState::Failed, None, Some(&e));
+            return;
+        }
+    };
+
+    // 4. Attente de la disponibilité. Sans condition déclarée, un script dont d'autres dépendent
+    // utilise le motif de disponibilité de son framework, s'il en a un.
+    let check = node.settings.readiness.clone().or_else(|| {
+        node.has_dependents
+            .then(|| detect_ready_pattern(&path))
+            .flatten()
+            .map(|pattern| ReadinessCheck::Output { pattern })
+    });
+    let readiness = match check {
+        Some(check) => {
+            let timeout = node
+                .settings
+                .readiness_timeout_secs
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_READINESS_TIMEOUT);
+            wait_ready(&check, &run_id, &mut events, timeout).await
+        }
+        None => Ok(Readiness::Ready),
+    };
+
+    match readiness {
+        Ok(Readiness::Completed) => {
+            info!("Script '{}' terminé pour le projet ID '{}'", node.script, node.project_id);
+            report(NodeState::Completed, Some(&run_id), None);
+        }
+        Ok(Readiness::Ready) => {
+            info!("Script '{}' prêt pour le projet ID '{}'", node.script, node.project_id);
+            report(NodeState::Ready, Some(&run_id), None);
+            // Les dépendants qui attendent encore d'autres prérequis échoueront si ce script s'arrête.
+            // Un script prêt qui se termine, même avec succès, ne l'est plus pour ses dépendants.
+            wait_exit(&run_id, &mut events).await;
+            report(NodeState::Exited, Some(&run_id), None);
+        }
+        Err(e) => {
+            warn!("Script '{}' indisponible pour le projet ID '{}' : {}", node.script, node.project_id, e);
+            report(NodeState::Failed, Some(&run_id), Some(&e));
+        }
+    }
+}
+
+/// Attend la fin d'une exécution.
+async fn wait_exit(run_id: &str, events: &mut broadcast::Receiver<RunEvent>) {
+    loop {
+        match events.recv().await {
+            Ok(RunEvent::Exited { run_id: id, .. }) if id == run_id => return,
+            Ok(_) | Err(RecvError::Lagged(_)) => {}
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(project_id: Option<&str>, name: &str) -> ScriptRef {
+        ScriptRef { project_id: project_id.map(str::to_string), script: name.to_string(), source: None }
+    }
+
+    /// Projet dont chaque script dépend de ceux listés (du même projet ou `projet:script`).
+    fn project(id: &str, dependencies: &[(&str, &[&str])]) -> ProjectConfig {
+        ProjectConfig {
+            id: id.to_string(),
+            path: format!("/nonexistent/{}", id),
+            name: id.to_string(),
+            framework: String::new(),
+            framework_url: None,
+            git_remote: None,
+            package_name: None,
+            framework_version: None,
+            script_settings: dependencies
+                .iter()
+                .map(|(name, depends_on)| {
+                    let depends_on = depends_on
+                        .iter()
+                        .map(|dependency| match dependency.split_once(':') {
+                            Some((project_id, name)) => script(Some(project_id), name),
+                            None => script(None, dependency),
+                        })
+                        .collect();
+                    (name.to_string(), ScriptSettings { depends_on, ..Default::default() })
+                })
+                .collect(),
+            schedules: Vec::new(),
+        }
+    }
+
+    fn names(nodes: &[GraphNode]) -> Vec<String> {
+        nodes.iter().map(|node| format!("{}:{}", node.project_id, node.script)).collect()
+    }
+
+    #[test]
+    fn orders_prerequisites_first() {
+        let projects = [
+            project("api", &[("dev", &["db:up", "migrate"]), ("migrate", &["db:up"])]),
+            project("db", &[]),
+        ];
+        let nodes = build_graph(&projects, &script(Some("api"), "dev")).unwrap();
+        assert_eq!(names(&nodes), ["db:up", "api:migrate", "api:dev"]);
+
+        // Le prérequis partagé n'apparaît qu'une fois.
+        assert_eq!(nodes[2].depends_on, [0, 1]);
+        assert_eq!(nodes[1].depends_on, [0]);
+        assert!(nodes[0].has_dependents && nodes[1].has_dependents && !nodes[2].has_dependents);
+    }
+
+    #[test]
+    fn script_without_settings_is_a_single_node() {
+        let nodes = build_graph(&[project("web", &[])], &script(Some("web"), "dev")).unwrap();
+        assert_eq!(names(&nodes), ["web:dev"]);
+        assert!(nodes[0].depends_on.is_empty());
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let projects = [project("a", &[("dev", &["build"]), ("build", &["b:gen"])]), project("b", &[("gen", &["a:dev"])])];
+        assert_eq!(
+            build_graph(&projects, &script(Some("a"), "dev")).unwrap_err(),
+            "Cycle de dépendances : a:dev -> a:build -> b:gen -> a:dev"
+        );
+
+        let projects = [project("a", &[("dev", &["dev"])])];
+        assert!(build_graph(&projects, &script(Some("a"), "dev")).unwrap_err().starts_with("Cycle"));
+    }
+
+    #[test]
+    fn rejects_unknown_projects() {
+        let projects = [project("a", &[("dev", &["missing:up"])])];
+        assert!(build_graph(&projects, &script(Some("a"), "dev")).is_err());
+        assert!(build_graph(&projects, &script(None, "dev")).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+// src-tauri/src/health.rs
+
+use crate::package_json::read_package_json;
+use crate::project_type::has_manifest;
+use crate::types::{ProjectConfig, ProjectHealth};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Profondeur maximale de recherche d'un projet déplacé sous une racine connue.
+const MAX_SEARCH_DEPTH: usize = 3;
+
+/// Dossiers ignorés lors de la recherche d'un projet déplacé.
+const IGNORED_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "vendor"];
+
+/// Calcule l'état de santé d'un projet à partir de son dossier.
+pub fn check_project_health(path: &Path) -> ProjectHealth {
+    health_from_package_json(path, read_package_json(path).as_ref())
+}
+
+/// Calcule l'état de santé d'un projet à partir de son package.json déjà analysé
+/// (`None` s'il est absent ou illisible), sans relire le fichier.
+pub fn health_from_package_json(path: &Path, package_json: Option<&Value>) -> ProjectHealth {
+    if !path.is_dir() {
+        return ProjectHealth::MissingDirectory;
+    }
+
+    if !has_manifest(path) {
+        return ProjectHealth::MissingManifest;
+    }
+
+    // Un package.json présent mais illisible reste signalé, même si d'autres manifestes existent.
+    if package_json.is_none() && path.join("package.json").is_file() {
+        return ProjectHealth::InvalidPackageJson;
+    }
+    ProjectHealth::Ok
+}
+
+/// Extrait le champ "name" du package.json d'un projet.
+pub fn read_package_name(path: &Path) -> Option<String> {
+    read_package_json(path)?
+        .get("name")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Extrait l'URL du remote git "origin" (ou du premier remote trouvé) d'un projet.
+pub fn read_git_remote(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path.join(".git").join("config")).ok()?;
+
+    let mut current_remote: Option<String> = None;
+    let mut first_url: Option<String> = None;
+
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            current_remote = line
+                .strip_prefix("[remote \"")
+                .and_then(|rest| rest.strip_suffix("\"]"))
+                .map(str::to_string);
+            continue;
+        }
+
+        let Some(remote) = current_remote.as_deref() else {
+            continue;
+        };
+
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim() == "url" {
+                let url = value.trim().to_string();
+                if remote == "origin" {
+                    return Some(url);
+                }
+                first_url.get_or_insert(url);
+            }
+        }
+    }
+
+    first_url
+}
+
+/// Tente de retrouver un projet déplacé sous les racines fournies en comparant
+/// son remote git, puis le nom déclaré dans son package.json.
+pub fn locate_moved_project(project: &ProjectConfig, roots: &[PathBuf]) -> Option<PathBuf> {
+    if project.git_remote.is_none() && project.package_name.is_none() {
+        return None;
+    }
+
+    let mut candidates = Vec::new();
+    let mut visited = HashSet::new();
+    for root in roots {
+        collect_candidates(root, 0, &mut visited, &mut candidates);
+    }
+
+    let original_path = PathBuf::from(&project.path);
+    let candidates: Vec<PathBuf> = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != original_path)
+        .collect();
+
+    if let Some(remote) = project.git_remote.as_deref() {
+        if let Some(found) = candidates
+            .iter()
+            .find(|candidate| read_git_remote(candidate).as_deref() == Some(remote))
+        {
+            return Some(found.clone());
+        }
+    }
+
+    if let Some(name) = project.package_name.as_deref() {
+        if let Some(found) = candidates
+            .iter()
+            .find(|candidate| read_package_name(candidate).as_deref() == Some(name))
+        {
+            return Some(found.clone());
+        }
+    }
+
+    None
+}
+
+/// Parcourt récursivement une racine à la recherche de dossiers contenant un manifeste de projet.
+fn collect_candidates(
+    dir: &Path,
+    depth: usize,
+    visited: &mut HashSet<PathBuf>,
+    candidates: &mut Vec<PathBuf>,
+) {
+    if depth > MAX_SEARCH_DEPTH || !visited.insert(dir.to_path_buf()) {
+        return;
+    }
+
+    if has_manifest(dir) {
+        candidates.push(dir.to_path_buf());
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let entry_path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+
+        if file_name.starts_with('.') || IGNORED_DIRS.contains(&file_name.as_str()) {
+            continue;
+        }
+
+        if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            collect_candidates(&entry_path, depth + 1, visited, candidates);
+        }
+    }
+}
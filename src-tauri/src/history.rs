@@ -0,0 +1,291 @@
+// src-tauri/src/history.rs
+
+use crate::limits::LimitExceeded;
+use crate::runner::RunTrigger;
+use log::error;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+const HISTORY_FILE_NAME: &str = ".dld-runs.jsonl";
+
+fn default_page_size() -> usize {
+    50
+}
+
+/// Tentative terminée d'une exécution : un script redémarré produit un enregistrement par lancement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub run_id: String,
+    pub project_id: String,
+    pub script: String,
+    pub program: String,
+    pub args: Vec<String>,
+    pub trigger: RunTrigger,
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    /// Signal ayant mis fin au processus (Unix uniquement).
+    pub signal: Option<i32>,
+    /// Nombre de redémarrages ayant précédé cette tentative (0 pour le premier lancement).
+    #[serde(default)]
+    pub restarts: u32,
+    #[serde(default)]
+    pub limit: Option<LimitExceeded>,
+}
+
+/// Critères de recherche dans l'historique, du plus récent au plus ancien.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RunFilter {
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub script: Option<String>,
+    #[serde(default)]
+    pub trigger: Option<RunTrigger>,
+    #[serde(default)]
+    pub success: Option<bool>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default = "default_page_size")]
+    pub limit: usize,
+}
+
+impl RunFilter {
+    fn matches(&self, record: &RunRecord) -> bool {
+        self.project_id.iter().all(|id| *id == record.project_id)
+            && self.script.iter().all(|script| *script == record.script)
+            && self.trigger.iter().all(|trigger| *trigger == record.trigger)
+            && self.success.iter().all(|success| *success == record.success)
+    }
+}
+
+/// Page de résultats de l'historique.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunPage {
+    /// Nombre total d'exécutions correspondant aux critères.
+    pub total: usize,
+    pub runs: Vec<RunRecord>,
+}
+
+/// Statistiques d'exécution d'un script.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptStats {
+    pub project_id: String,
+    pub script: String,
+    pub runs: usize,
+    pub success_rate: f64,
+    pub median_duration_ms: u64,
+    pub last_run_at: String,
+    pub last_success_at: Option<String>,
+}
+
+/// Historique des exécutions, conservé dans un fichier JSON Lines en ajout seul.
+pub struct RunHistory {
+    path: PathBuf,
+    /// Empêche l'entrelacement de deux écritures.
+    write_lock: Mutex<()>,
+}
+
+impl Default for RunHistory {
+    fn default() -> Self {
+        RunHistory {
+            path: get_history_file_path(),
+            write_lock: Mutex::new(()),
+        }
+    }
+}
+
+/// Obtient le chemin du fichier d'historique, à côté du fichier de configuration.
+pub fn get_history_file_path() -> PathBuf {
+    dirs::home_dir()
+        .expect("Impossible de trouver le dossier utilisateur")
+        .join(HISTORY_FILE_NAME)
+}
+
+impl RunHistory {
+    /// Ajoute une exécution à la fin de l'historique.
+    pub fn append(&self, record: &RunRecord) {
+        let _writing = self.write_lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let result = serde_json::to_string(record).map_err(std::io::Error::from).and_then(|line| {
+            let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+            writeln!(file, "{}", line)
+        });
+        if let Err(e) = result {
+            error!("Erreur lors de l'enregistrement de l'exécution {}: {}", record.run_id, e);
+        }
+    }
+
+    /// Lit l'historique complet, dans l'ordre d'enregistrement. Les lignes illisibles sont ignorées.
+    fn read(&self) -> Vec<RunRecord> {
+        let Ok(file) = File::open(&self.path) else {
+            return Vec::new();
+        };
+        BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| serde_json::from_str(&line).ok())
+            .collect()
+    }
+
+    /// Retourne une page d'exécutions correspondant aux critères, de la plus récente à la plus ancienne.
+    pub fn list(&self, filter: &RunFilter) -> RunPage {
+        let matching: Vec<RunRecord> = self.read().into_iter().rev().filter(|record| filter.matches(record)).collect();
+        RunPage {
+            total: matching.len(),
+            runs: matching.into_iter().skip(filter.offset).take(filter.limit).collect(),
+        }
+    }
+
+    /// Calcule les statistiques de chaque script, éventuellement limitées à un projet.
+    pub fn stats(&self, project_id: Option<&str>) -> Vec<ScriptStats> {
+        let mut by_script: HashMap<(String, String), Vec<RunRecord>> = HashMap::new();
+        for record in self.read() {
+            if project_id.is_some_and(|id| id != record.project_id) {
+                continue;
+            }
+            by_script
+                .entry((record.project_id.clone(), record.script.clone()))
+                .or_default()
+                .push(record);
+        }
+
+        let mut stats: Vec<ScriptStats> = by_script
+            .into_iter()
+            .map(|((project_id, script), records)| script_stats(project_id, script, &records))
+            .collect();
+        stats.sort_by(|a, b| (&a.project_id, &a.script).cmp(&(&b.project_id, &b.script)));
+        stats
+    }
+}
+
+/// Durée écoulée depuis un instant RFC 3339, en millisecondes (0 s'il est illisible).
+pub fn elapsed_ms(started_at: &str, ended_at: &chrono::DateTime<chrono::Local>) -> u64 {
+    chrono::DateTime::parse_from_rfc3339(started_at)
+        .map(|started_at| ended_at.signed_duration_since(started_at).num_milliseconds().max(0) as u64)
+        .unwrap_or_default()
+}
+
+/// Statistiques d'un script à partir de ses exécutions, dans l'ordre d'enregistrement.
+fn script_stats(project_id: String, script: String, records: &[RunRecord]) -> ScriptStats {
+    let successes = records.iter().filter(|record| record.success).count();
+    let mut durations: Vec<u64> = records.iter().map(|record| record.duration_ms).collect();
+    durations.sort_unstable();
+    let middle = durations.len() / 2;
+    let median_duration_ms = if durations.len() % 2 == 0 {
+        (durations[middle - 1] + durations[middle]) / 2
+    } else {
+        durations[middle]
+    };
+
+    ScriptStats {
+        project_id,
+        script,
+        runs: records.len(),
+        success_rate: successes as f64 / records.len() as f64,
+        median_duration_ms,
+        last_run_at: records.last().map(|record| record.ended_at.clone()).unwrap_or_default(),
+        last_success_at: records
+            .iter()
+            .rev()
+            .find(|record| record.success)
+            .map(|record| record.ended_at.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(run_id: &str, script: &str, duration_ms: u64, success: bool) -> RunRecord {
+        RunRecord {
+            run_id: run_id.to_string(),
+            project_id: "project".to_string(),
+            script: script.to_string(),
+            program: "npm".to_string(),
+            args: vec!["run".to_string(), script.to_string()],
+            trigger: RunTrigger::Manual,
+            started_at: "2024-05-01T10:00:00+02:00".to_string(),
+            ended_at: format!("2024-05-01T10:00:{:02}+02:00", duration_ms / 1000),
+            duration_ms,
+            success,
+            exit_code: Some(if success { 0 } else { 1 }),
+            signal: None,
+            restarts: 0,
+            limit: None,
+        }
+    }
+
+    fn history_with(records: &[RunRecord]) -> (tempfile::TempDir, RunHistory) {
+        let dir = tempfile::tempdir().unwrap();
+        let history = RunHistory {
+            path: dir.path().join(HISTORY_FILE_NAME),
+            write_lock: Mutex::new(()),
+        };
+        for record in records {
+            history.append(record);
+        }
+        (dir, history)
+    }
+
+    #[test]
+    fn computes_median_and_success_rate() {
+        let records = [
+            record("1", "build", 4_000, true),
+            record("2", "build", 1_000, false),
+            record("3", "build", 3_000, true),
+        ];
+        let stats = script_stats("project".to_string(), "build".to_string(), &records);
+        assert_eq!(stats.runs, 3);
+        assert_eq!(stats.median_duration_ms, 3_000);
+        assert!((stats.success_rate - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(stats.last_run_at, records[2].ended_at);
+        assert_eq!(stats.last_success_at.as_deref(), Some(records[2].ended_at.as_str()));
+
+        let stats = script_stats("project".to_string(), "build".to_string(), &records[..2]);
+        assert_eq!(stats.median_duration_ms, 2_500);
+        assert_eq!(stats.success_rate, 0.5);
+        assert_eq!(stats.last_success_at.as_deref(), Some(records[0].ended_at.as_str()));
+    }
+
+    #[test]
+    fn lists_pages_from_most_recent() {
+        let records: Vec<RunRecord> = (0..5)
+            .map(|index| record(&index.to_string(), if index % 2 == 0 { "dev" } else { "test" }, 1_000, index != 3))
+            .collect();
+        let (_dir, history) = history_with(&records);
+        let ids = |page: RunPage| (page.total, page.runs.into_iter().map(|run| run.run_id).collect::<Vec<_>>());
+
+        let filter = RunFilter { limit: 2, ..Default::default() };
+        assert_eq!(ids(history.list(&filter)), (5, vec!["4".to_string(), "3".to_string()]));
+        let filter = RunFilter { offset: 4, limit: 2, ..Default::default() };
+        assert_eq!(ids(history.list(&filter)), (5, vec!["0".to_string()]));
+        let filter = RunFilter { offset: 10, limit: 2, ..Default::default() };
+        assert_eq!(ids(history.list(&filter)), (5, Vec::new()));
+
+        let filter = RunFilter { script: Some("test".to_string()), success: Some(true), limit: 10, ..Default::default() };
+        assert_eq!(ids(history.list(&filter)), (1, vec!["1".to_string()]));
+    }
+
+    #[test]
+    fn groups_stats_by_script() {
+        let (_dir, history) = history_with(&[record("1", "dev", 1_000, true), record("2", "build", 2_000, false)]);
+        let stats = history.stats(Some("project"));
+        assert_eq!(stats.iter().map(|stats| stats.script.as_str()).collect::<Vec<_>>(), ["build", "dev"]);
+        assert!(history.stats(Some("other")).is_empty());
+    }
+
+    #[test]
+    fn computes_elapsed_time() {
+        let ended_at = chrono::DateTime::parse_from_rfc3339("2024-05-01T10:00:02.500+02:00")
+            .unwrap()
+            .with_timezone(&chrono::Local);
+        assert_eq!(elapsed_ms("2024-05-01T10:00:00+02:00", &ended_at), 2_500);
+        assert_eq!(elapsed_ms("invalide", &ended_at), 0);
+    }
+}
@@ -1,21 +1,53 @@
 mod commands;
+mod compose;
 mod config;
 mod framework;
+mod graph;
+mod health;
+mod history;
+mod limits;
+mod metrics;
+mod node_version;
+mod package_json;
+mod pidfile;
+mod probes;
+mod profiles;
+mod readiness;
+mod project_type;
+mod queue;
+mod runner;
+mod scheduler;
 mod script;
+mod store;
+mod toolchain;
+mod triggers;
 mod types;
+mod versions;
+mod watcher;
 
-use config::load_or_initialize_config;
+use log::error;
+use tauri::{Emitter, Manager, RunEvent};
 use types::AppState;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .manage(AppState {
-            projects: tauri::async_runtime::Mutex::new(Vec::new()),
-        })
         .plugin(tauri_plugin_shell::init())
         .setup(|app| {
-            load_or_initialize_config();
+            app.manage(AppState::load());
+            let state = app.state::<AppState>();
+            let handle = app.handle().clone();
+            state.store.on_persist_error(move |message| {
+                if let Err(e) = handle.emit("config_error", message) {
+                    error!("Erreur lors de l'émission de config_error: {}", e);
+                }
+            });
+            state.watcher.start(app.handle().clone(), &state.store.projects());
+            state.watch_triggers.start(app.handle().clone(), &state.store.projects());
+            pidfile::adopt_orphans(app.handle().clone());
+            metrics::start(app.handle().clone());
+            queue::start(app.handle().clone());
+            scheduler::start(app.handle().clone());
 
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -29,11 +61,50 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             commands::fetch_projects,
+            commands::fetch_projects_detailed,
             commands::fetch_package_json,
             commands::run_script_project,
+            commands::fetch_tasks,
+            commands::run_task,
+            commands::fetch_compose_services,
+            commands::compose_service_action,
+            commands::stop_script,
+            commands::restart_script,
+            commands::get_script_metrics,
+            commands::list_runs,
+            commands::fetch_run_stats,
+            commands::schedule_list,
+            commands::schedule_save,
+            commands::schedule_delete,
+            commands::schedule_enable,
+            commands::schedule_disable,
+            commands::fetch_run_queue,
+            commands::reorder_queued_run,
+            commands::cancel_queued_run,
+            commands::fetch_concurrency_limits,
+            commands::update_concurrency_limits,
+            commands::fetch_profiles,
+            commands::save_profile,
+            commands::delete_profile,
+            commands::start_profile,
+            commands::stop_profile,
+            commands::get_profile_status,
+            commands::update_script_settings,
+            commands::start_script_graph,
             commands::add_project,
-            commands::remove_project
+            commands::remove_project,
+            commands::relocate_project,
+            commands::app_state_snapshot,
+            commands::check_toolchain,
+            commands::check_node_version,
+            commands::fetch_framework_rules
         ])
-        .run(tauri::generate_context!())
-        .expect("Erreur lors de l'exécution de l'application Tauri");
+        .build(tauri::generate_context!())
+        .expect("Erreur lors de l'exécution de l'application Tauri")
+        .run(|app, event| {
+            // Les modifications encore en attente d'écriture ne doivent pas être perdues à la fermeture.
+            if let RunEvent::ExitRequested { .. } = event {
+                app.state::<AppState>().store.flush();
+            }
+        });
 }
\ No newline at end of file
@@ -0,0 +1,194 @@
+// src-tauri/src/limits.rs
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+
+/// Racine de la hiérarchie cgroup v2.
+#[cfg(target_os = "linux")]
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Limites de ressources appliquées à un script à son lancement.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Mémoire maximale, en mégaoctets : cgroup v2 si disponible, mesure périodique de la mémoire
+    /// résidente du script et de ses enfants sinon (`RLIMIT_AS` ferait échouer Node et V8, qui
+    /// réservent bien plus d'espace d'adressage qu'ils n'en utilisent).
+    #[serde(default)]
+    pub max_memory_mb: Option<u64>,
+    /// Priorité d'ordonnancement (`nice`), de -20 à 19. Une priorité négative exige des privilèges :
+    /// sans eux, le script garde la priorité de l'application.
+    #[serde(default)]
+    pub nice: Option<i32>,
+    #[serde(default)]
+    pub max_open_files: Option<u64>,
+    /// Durée maximale d'exécution, en secondes, au-delà de laquelle le script est arrêté sans redémarrage.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn max_memory_bytes(&self) -> Option<u64> {
+        self.max_memory_mb.map(|mb| mb.saturating_mul(1024 * 1024))
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+}
+
+/// Limite ayant provoqué l'arrêt d'un script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitExceeded {
+    Memory,
+    Timeout,
+}
+
+/// Limites appliquées dans le processus enfant, entre `fork` et `exec`.
+/// Ne contient que des valeurs copiables : aucune allocation n'est permise à ce stade.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChildLimits {
+    pub nice: Option<i32>,
+    pub max_open_files: Option<u64>,
+    /// Descripteur du fichier `cgroup.procs` du groupe dans lequel placer le processus.
+    pub cgroup_procs: Option<std::os::fd::RawFd>,
+}
+
+#[cfg(unix)]
+impl ChildLimits {
+    pub fn is_empty(&self) -> bool {
+        self.nice.is_none() && self.max_open_files.is_none() && self.cgroup_procs.is_none()
+    }
+
+    /// Applique les limites au processus courant. À appeler uniquement depuis `pre_exec`.
+    pub fn apply(self) -> std::io::Result<()> {
+        if let Some(fd) = self.cgroup_procs {
+            // Écrire 0 dans `cgroup.procs` y déplace le processus appelant.
+            // SAFETY: le descripteur est ouvert par le parent et reste valide jusqu'à `exec`.
+            if unsafe { libc::write(fd, b"0".as_ptr().cast(), 1) } != 1 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        if let Some(files) = self.max_open_files {
+            set_rlimit(libc::RLIMIT_NOFILE, files)?;
+        }
+        if let Some(nice) = self.nice {
+            // Sans privilèges, abaisser la priorité échoue (EACCES) : le script est tout de même lancé.
+            // SAFETY: setpriority ne modifie que la priorité du processus courant (`who` = 0).
+            let _ = unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice.clamp(-20, 19)) };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type RlimitResource = libc::c_int;
+
+/// Abaisse une limite, sans dépasser la limite maximale déjà en vigueur.
+#[cfg(unix)]
+fn set_rlimit(resource: RlimitResource, value: u64) -> std::io::Result<()> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `limit` est une structure valide dont getrlimit et setrlimit ne conservent aucune référence.
+    unsafe {
+        if libc::getrlimit(resource, &mut limit) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let value = (value as libc::rlim_t).min(limit.rlim_max);
+        limit.rlim_cur = value;
+        limit.rlim_max = value;
+        if libc::setrlimit(resource, &limit) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Groupe de contrôle cgroup v2 limitant la mémoire d'une exécution et de ses enfants,
+/// supprimé lorsqu'il n'est plus utilisé.
+pub struct MemoryCgroup {
+    #[cfg(target_os = "linux")]
+    path: PathBuf,
+}
+
+impl MemoryCgroup {
+    /// Crée un groupe à côté de celui de l'application. Échoue si cgroup v2 n'est pas monté
+    /// ou si le contrôleur mémoire n'est pas délégué à l'utilisateur.
+    #[cfg(target_os = "linux")]
+    pub fn create(run_id: &str, max_bytes: u64) -> Option<Self> {
+        // Hiérarchie unifiée uniquement : en mode hybride, `/sys/fs/cgroup` est un simple tmpfs.
+        if !Path::new(CGROUP_ROOT).join("cgroup.controllers").is_file() {
+            return None;
+        }
+        let own = fs::read_to_string("/proc/self/cgroup").ok()?;
+        let relative = own.lines().find_map(|line| line.strip_prefix("0::"))?.trim_start_matches('/');
+        let own_path = Path::new(CGROUP_ROOT).join(relative);
+        // Un groupe contenant des processus ne peut pas avoir d'enfants avec des contrôleurs actifs.
+        let parent = if relative.is_empty() { own_path.as_path() } else { own_path.parent()? };
+
+        let path = parent.join(format!("dev-launcher-{}", run_id));
+        fs::create_dir(&path).ok()?;
+        let cgroup = MemoryCgroup { path };
+        cgroup.write("memory.max", &max_bytes.to_string()).ok()?;
+        // Sans cette limite, la mémoire excédentaire serait déplacée vers le swap au lieu d'arrêter le script.
+        let _ = cgroup.write("memory.swap.max", "0");
+        Some(cgroup)
+    }
+
+    /// Écrit dans un fichier de contrôle existant : son absence signifie que le contrôleur n'est pas délégué.
+    #[cfg(target_os = "linux")]
+    fn write(&self, file: &str, value: &str) -> std::io::Result<()> {
+        use std::io::Write;
+        fs::OpenOptions::new()
+            .write(true)
+            .open(self.path.join(file))?
+            .write_all(value.as_bytes())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn create(_run_id: &str, _max_bytes: u64) -> Option<Self> {
+        None
+    }
+
+    /// Ouvre le fichier `cgroup.procs` pour y placer un processus au lancement.
+    #[cfg(target_os = "linux")]
+    pub fn open_procs(&self) -> std::io::Result<fs::File> {
+        fs::OpenOptions::new().write(true).open(self.path.join("cgroup.procs"))
+    }
+
+    /// Nombre de processus du groupe tués pour dépassement de la mémoire maximale.
+    #[cfg(target_os = "linux")]
+    pub fn oom_kills(&self) -> u64 {
+        fs::read_to_string(self.path.join("memory.events"))
+            .ok()
+            .and_then(|events| {
+                events
+                    .lines()
+                    .find_map(|line| line.strip_prefix("oom_kill "))
+                    .and_then(|count| count.trim().parse().ok())
+            })
+            .unwrap_or(0)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn oom_kills(&self) -> u64 {
+        0
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for MemoryCgroup {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir(&self.path);
+    }
+}
@@ -0,0 +1,153 @@
+// src-tauri/src/metrics.rs
+
+use crate::types::AppState;
+use log::error;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Intervalle entre deux mesures.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+/// Nombre de mesures conservées par exécution (deux minutes).
+const HISTORY_LEN: usize = 60;
+
+/// Consommation de ressources de l'arborescence de processus d'une exécution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSample {
+    pub timestamp: String,
+    /// Somme de l'utilisation CPU des processus, en pourcentage d'un cœur (peut dépasser 100).
+    pub cpu_percent: f32,
+    /// Mémoire résidente totale, en octets.
+    pub memory_bytes: u64,
+    /// Nombre de processus enfants du processus principal.
+    pub children: usize,
+}
+
+/// Historique récent des mesures de chaque exécution.
+#[derive(Default)]
+pub struct MetricsHistory {
+    samples: Mutex<HashMap<String, VecDeque<MetricsSample>>>,
+}
+
+impl MetricsHistory {
+    /// Retourne les mesures conservées pour une exécution, de la plus ancienne à la plus récente.
+    pub fn get(&self, run_id: &str) -> Option<Vec<MetricsSample>> {
+        self.samples
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(run_id)
+            .map(|samples| samples.iter().cloned().collect())
+    }
+
+    fn push(&self, run_id: &str, sample: MetricsSample) {
+        let mut samples = self.samples.lock().unwrap_or_else(PoisonError::into_inner);
+        let history = samples.entry(run_id.to_string()).or_default();
+        if history.len() == HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(sample);
+    }
+
+    /// Oublie les exécutions terminées.
+    fn retain(&self, run_ids: &HashSet<String>) {
+        self.samples
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|run_id, _| run_ids.contains(run_id));
+    }
+}
+
+/// Démarre la mesure périodique des scripts en cours (`script_metrics`).
+pub fn start<R: Runtime>(app: AppHandle<R>) {
+    thread::spawn(move || {
+        let mut system = System::new();
+        let refresh = ProcessRefreshKind::new().with_cpu().with_memory();
+        loop {
+            thread::sleep(SAMPLE_INTERVAL);
+            let state = app.state::<AppState>();
+            let processes = state.store.processes();
+            state
+                .metrics
+                .retain(&processes.iter().map(|process| process.run_id.clone()).collect());
+            if processes.is_empty() {
+                continue;
+            }
+
+            system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
+            let children = children_by_parent(&system);
+            for process in processes {
+                // Processus en attente de redémarrage : rien à mesurer.
+                let Some(pid) = process.pid else {
+                    continue;
+                };
+                let Some(sample) = sample_tree(&system, &children, Pid::from_u32(pid)) else {
+                    continue;
+                };
+                state.metrics.push(&process.run_id, sample.clone());
+
+                let payload = json!({
+                    "id": process.project_id,
+                    "run_id": process.run_id,
+                    "metrics": sample,
+                });
+                if let Err(e) = app.emit("script_metrics", payload) {
+                    error!("Erreur lors de l'émission de script_metrics: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Mémoire résidente totale d'un processus et de ses descendants, en octets.
+/// Sert à limiter la mémoire d'un script lorsque cgroup v2 n'est pas disponible.
+pub fn tree_memory_bytes(pid: u32) -> Option<u64> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::new().with_memory());
+    let children = children_by_parent(&system);
+    sample_tree(&system, &children, Pid::from_u32(pid)).map(|sample| sample.memory_bytes)
+}
+
+/// Index des processus enfants de chaque processus.
+fn children_by_parent(system: &System) -> HashMap<Pid, Vec<Pid>> {
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for (pid, process) in system.processes() {
+        // Les threads apparaissent comme des processus sous Linux : seuls les processus sont comptés.
+        if process.thread_kind().is_some() {
+            continue;
+        }
+        if let Some(parent) = process.parent() {
+            children.entry(parent).or_default().push(*pid);
+        }
+    }
+    children
+}
+
+/// Agrège la consommation d'un processus et de tous ses descendants.
+fn sample_tree(system: &System, children: &HashMap<Pid, Vec<Pid>>, root: Pid) -> Option<MetricsSample> {
+    system.process(root)?;
+
+    let mut sample = MetricsSample {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        cpu_percent: 0.0,
+        memory_bytes: 0,
+        children: 0,
+    };
+    let mut pending = vec![root];
+    while let Some(pid) = pending.pop() {
+        let Some(process) = system.process(pid) else {
+            continue;
+        };
+        sample.cpu_percent += process.cpu_usage();
+        sample.memory_bytes += process.memory();
+        if pid != root {
+            sample.children += 1;
+        }
+        pending.extend(children.get(&pid).into_iter().flatten().copied());
+    }
+    Some(sample)
+}
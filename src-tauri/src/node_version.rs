@@ -0,0 +1,371 @@
+// src-tauri/src/node_version.rs
+
+use crate::package_json::read_package_json;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version de Node exigée par un projet et fichier d'où elle provient.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeRequirement {
+    pub spec: String,
+    pub source: String,
+}
+
+/// Version de Node installée par un gestionnaire de versions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledNode {
+    pub version: String,
+    pub manager: String,
+    pub bin_dir: String,
+}
+
+/// Résultat de la résolution de la version de Node d'un projet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeResolution {
+    pub requirement: Option<NodeRequirement>,
+    pub resolved: Option<InstalledNode>,
+    pub mismatch: bool,
+}
+
+type Version = (u64, u64, u64);
+
+/// Noms de code des versions LTS de Node et version majeure correspondante.
+const LTS_CODENAMES: &[(&str, u64)] = &[
+    ("argon", 4),
+    ("boron", 6),
+    ("carbon", 8),
+    ("dubnium", 10),
+    ("erbium", 12),
+    ("fermium", 14),
+    ("gallium", 16),
+    ("hydrogen", 18),
+    ("iron", 20),
+    ("jod", 22),
+    ("krypton", 24),
+];
+
+/// Lit la version de Node exigée par un projet (.nvmrc, .node-version, .tool-versions, volta.node, engines.node).
+pub fn read_node_requirement(project_dir: &Path) -> Option<NodeRequirement> {
+    for file_name in [".nvmrc", ".node-version"] {
+        let spec = fs::read_to_string(project_dir.join(file_name)).ok().and_then(|contents| {
+            contents
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+        });
+        if let Some(spec) = spec {
+            return Some(NodeRequirement {
+                spec,
+                source: file_name.to_string(),
+            });
+        }
+    }
+
+    if let Ok(contents) = fs::read_to_string(project_dir.join(".tool-versions")) {
+        for line in contents.lines() {
+            let mut parts = line.split_whitespace();
+            if matches!(parts.next(), Some("nodejs") | Some("node")) {
+                if let Some(spec) = parts.next() {
+                    return Some(NodeRequirement {
+                        spec: spec.to_string(),
+                        source: ".tool-versions".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    let package_json = read_package_json(project_dir)?;
+    [("volta", "package.json (volta.node)"), ("engines", "package.json (engines.node)")]
+        .into_iter()
+        .find_map(|(key, source)| {
+            let spec = package_json.get(key)?.get("node")?.as_str()?;
+            Some(NodeRequirement {
+                spec: spec.to_string(),
+                source: source.to_string(),
+            })
+        })
+}
+
+/// Résout la version de Node à utiliser pour un projet parmi les versions installées.
+/// `default_version` est la version du `node` présent dans le PATH, s'il y en a un.
+pub fn resolve_node(project_dir: &Path, default_version: Option<&str>) -> NodeResolution {
+    let Some(requirement) = read_node_requirement(project_dir) else {
+        return NodeResolution {
+            requirement: None,
+            resolved: None,
+            mismatch: false,
+        };
+    };
+
+    let resolved = installed_node_versions()
+        .into_iter()
+        .filter_map(|node| parse_version(&node.version).map(|version| (version, node)))
+        .filter(|(version, _)| matches_spec(&requirement.spec, *version))
+        .max_by_key(|(version, _)| *version)
+        .map(|(_, node)| node);
+
+    let default_matches = default_version
+        .and_then(parse_version)
+        .map(|version| matches_spec(&requirement.spec, version))
+        .unwrap_or(false);
+
+    NodeResolution {
+        mismatch: resolved.is_none() && !default_matches,
+        requirement: Some(requirement),
+        resolved,
+    }
+}
+
+/// Liste les versions de Node installées via nvm, fnm, Volta et asdf.
+fn installed_node_versions() -> Vec<InstalledNode> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+
+    let nvm_dir = env::var_os("NVM_DIR").map(PathBuf::from).unwrap_or_else(|| home.join(".nvm"));
+    let fnm_dirs = match env::var_os("FNM_DIR") {
+        Some(dir) => vec![PathBuf::from(dir)],
+        None => vec![home.join(".local").join("share").join("fnm"), home.join(".fnm")],
+    };
+    let volta_dir = env::var_os("VOLTA_HOME").map(PathBuf::from).unwrap_or_else(|| home.join(".volta"));
+    let asdf_dir = env::var_os("ASDF_DATA_DIR").map(PathBuf::from).unwrap_or_else(|| home.join(".asdf"));
+
+    let bin = |dir: PathBuf| if cfg!(windows) { dir } else { dir.join("bin") };
+
+    let mut installed = Vec::new();
+    scan_versions(&nvm_dir.join("versions").join("node"), "nvm", bin, &mut installed);
+    for fnm_dir in fnm_dirs {
+        scan_versions(
+            &fnm_dir.join("node-versions"),
+            "fnm",
+            |dir| bin(dir.join("installation")),
+            &mut installed,
+        );
+    }
+    scan_versions(
+        &volta_dir.join("tools").join("image").join("node"),
+        "volta",
+        bin,
+        &mut installed,
+    );
+    scan_versions(&asdf_dir.join("installs").join("nodejs"), "asdf", bin, &mut installed);
+    installed
+}
+
+/// Ajoute chaque sous-dossier de version d'un gestionnaire dont le dossier `bin` existe.
+fn scan_versions(
+    root: &Path,
+    manager: &str,
+    bin_dir: impl Fn(PathBuf) -> PathBuf,
+    installed: &mut Vec<InstalledNode>,
+) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let version = entry.file_name().to_string_lossy().trim_start_matches('v').to_string();
+        let dir = bin_dir(entry.path());
+        if parse_version(&version).is_some() && dir.is_dir() {
+            installed.push(InstalledNode {
+                version,
+                manager: manager.to_string(),
+                bin_dir: dir.to_string_lossy().to_string(),
+            });
+        }
+    }
+}
+
+/// Analyse une version complète `x.y.z` (préfixe `v` toléré).
+fn parse_version(version: &str) -> Option<Version> {
+    let mut parts = version.trim().trim_start_matches('v').split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().unwrap_or("0").parse().ok()?;
+    let patch = parts
+        .next()
+        .unwrap_or("0")
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()?;
+    Some((major, minor, patch))
+}
+
+/// Analyse une version partielle (`18`, `18.19`, `18.x`) en bornes [min, max).
+fn partial_bounds(partial: &str) -> Option<(Version, Version)> {
+    let parts: Vec<&str> = partial
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .take_while(|part| !matches!(*part, "x" | "X" | "*"))
+        .collect();
+
+    let numbers: Vec<u64> = parts.iter().map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    match numbers.as_slice() {
+        [] => Some(((0, 0, 0), (u64::MAX, 0, 0))),
+        [major] => Some(((*major, 0, 0), (major.checked_add(1)?, 0, 0))),
+        [major, minor] => Some(((*major, *minor, 0), (*major, minor.checked_add(1)?, 0))),
+        [major, minor, patch, ..] => Some(((*major, *minor, *patch), (*major, *minor, patch.checked_add(1)?))),
+    }
+}
+
+/// Vérifie qu'une version satisfait une spécification (.nvmrc ou plage semver d'engines).
+fn matches_spec(spec: &str, version: Version) -> bool {
+    let spec = spec.trim();
+    // Les alias (node, latest) acceptent n'importe quelle version installée.
+    if spec.is_empty() || spec == "*" || spec == "node" || spec == "latest" {
+        return true;
+    }
+    if let Some(line) = spec.strip_prefix("lts/").or_else(|| spec.strip_prefix("lts-")) {
+        return matches_lts(line, version);
+    }
+
+    spec.split("||").any(|set| {
+        let set = set.trim();
+        if let Some((low, high)) = set.split_once(" - ") {
+            return matches_comparator(&format!(">={}", low.trim()), version)
+                && matches_comparator(&format!("<={}", high.trim()), version);
+        }
+        comparators(set).iter().all(|comparator| matches_comparator(comparator, version))
+    })
+}
+
+/// Découpe un ensemble de comparateurs sur les espaces, en rattachant un opérateur isolé
+/// (`>= 18`) à la version qui le suit.
+fn comparators(set: &str) -> Vec<String> {
+    let mut comparators: Vec<String> = Vec::new();
+    let mut pending_operator: Option<&str> = None;
+    for token in set.split_whitespace() {
+        if matches!(token, ">=" | "<=" | ">" | "<" | "=" | "^" | "~") {
+            pending_operator = Some(token);
+            continue;
+        }
+        comparators.push(format!("{}{}", pending_operator.take().unwrap_or_default(), token));
+    }
+    // Un opérateur sans version est conservé : il ne correspond à aucune version.
+    comparators.extend(pending_operator.map(str::to_string));
+    comparators
+}
+
+/// Vérifie qu'une version appartient à une ligne LTS (`lts/hydrogen`) ; `lts/*` et les noms de code
+/// inconnus acceptent toute version majeure paire, seules à devenir LTS.
+fn matches_lts(line: &str, version: Version) -> bool {
+    let line = line.to_ascii_lowercase();
+    match LTS_CODENAMES.iter().find(|(codename, _)| *codename == line) {
+        Some((_, major)) => version.0 == *major,
+        None => version.0 >= 4 && version.0 % 2 == 0,
+    }
+}
+
+/// Vérifie un comparateur semver unique (`>=18`, `^18.2.0`, `~18.1`, `18.x`).
+fn matches_comparator(comparator: &str, version: Version) -> bool {
+    let (operator, partial) = match comparator.find(|c: char| c.is_ascii_digit() || c == 'x' || c == '*') {
+        Some(index) => comparator.split_at(index),
+        None => return false,
+    };
+    let Some((min, max)) = partial_bounds(partial) else {
+        return false;
+    };
+
+    match operator.trim_end_matches('v') {
+        ">=" => version >= min,
+        ">" => version >= max,
+        "<=" => version < max,
+        "<" => version < min,
+        "^" => {
+            let upper = if min.0 > 0 {
+                min.0.checked_add(1).map(|major| (major, 0, 0))
+            } else {
+                min.1.checked_add(1).map(|minor| (0, minor, 0))
+            };
+            version >= min && upper.map_or(true, |upper| version < upper)
+        }
+        "~" => {
+            let upper = if max.0 > min.0 {
+                Some(max)
+            } else {
+                min.1.checked_add(1).map(|minor| (min.0, minor, 0))
+            };
+            version >= min && upper.map_or(true, |upper| version < upper)
+        }
+        "" | "=" => version >= min && version < max,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn reads_requirement_from_version_files() {
+        let cases = [
+            (".nvmrc", "# projet\n\nv20.11.1\n", "v20.11.1", ".nvmrc"),
+            (".node-version", "18\n", "18", ".node-version"),
+            (".tool-versions", "python 3.12.1\nnodejs 20.10.0\n", "20.10.0", ".tool-versions"),
+            (
+                "package.json",
+                r#"{ "volta": { "node": "20.9.0" }, "engines": { "node": ">=18" } }"#,
+                "20.9.0",
+                "package.json (volta.node)",
+            ),
+            ("package.json", r#"{ "engines": { "node": "^18.17 || >=20" } }"#, "^18.17 || >=20", "package.json (engines.node)"),
+        ];
+
+        for (file_name, contents, spec, source) in cases {
+            let dir = tempfile::tempdir().unwrap();
+            fs::write(dir.path().join(file_name), contents).unwrap();
+            let requirement = read_node_requirement(dir.path()).unwrap();
+            assert_eq!((requirement.spec.as_str(), requirement.source.as_str()), (spec, source), "{}", file_name);
+        }
+    }
+
+    #[test]
+    fn nvmrc_takes_precedence_over_package_json() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(".nvmrc"), "lts/iron").unwrap();
+        fs::write(dir.path().join("package.json"), r#"{ "volta": { "node": "18.0.0" } }"#).unwrap();
+        assert_eq!(read_node_requirement(dir.path()).unwrap().source, ".nvmrc");
+    }
+
+    #[test]
+    fn matches_specs() {
+        let cases = [
+            ("20.11.1", (20, 11, 1), true),
+            ("v20.11.1", (20, 11, 2), false),
+            ("18", (18, 19, 0), true),
+            ("18", (19, 0, 0), false),
+            ("18.x", (18, 0, 1), true),
+            (">=18 <21", (20, 0, 0), true),
+            (">=18 <21", (21, 0, 0), false),
+            (">= 18", (18, 0, 0), true),
+            (">= 18", (16, 20, 0), false),
+            (">= 16 < 21", (20, 11, 0), true),
+            (">= 16 < 21", (21, 0, 0), false),
+            (">=18.0.0 < 21", (18, 19, 0), true),
+            ("^ 18.17 || >= 20", (22, 1, 0), true),
+            (">=", (20, 0, 0), false),
+            ("^18.17 || >=20", (18, 16, 0), false),
+            ("^18.17 || >=20", (22, 1, 0), true),
+            ("~20.9", (20, 10, 0), false),
+            ("16 - 18", (18, 5, 0), true),
+            ("node", (21, 0, 0), true),
+            ("lts/*", (20, 11, 0), true),
+            ("lts/*", (21, 6, 0), false),
+            ("lts/hydrogen", (18, 19, 0), true),
+            ("lts/hydrogen", (20, 0, 0), false),
+            ("lts/Iron", (20, 11, 0), true),
+            ("18446744073709551615", (u64::MAX, 0, 0), false),
+            ("^18446744073709551615", (u64::MAX, 1, 0), false),
+        ];
+
+        for (spec, version, expected) in cases {
+            assert_eq!(matches_spec(spec, version), expected, "{} / {:?}", spec, version);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// src-tauri/src/package_json.rs
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Lit et analyse le package.json d'un projet.
+pub fn read_package_json(project_dir: &Path) -> Option<Value> {
+    let contents = fs::read_to_string(project_dir.join("package.json")).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Cache des package.json analysés, invalidé par la date de modification du fichier.
+#[derive(Default)]
+pub struct PackageJsonCache {
+    entries: Mutex<HashMap<PathBuf, (SystemTime, Arc<Value>)>>,
+}
+
+impl PackageJsonCache {
+    /// Retourne le package.json analysé d'un projet, en ne le relisant que s'il a été modifié.
+    pub fn get(&self, project_dir: &Path) -> Option<Arc<Value>> {
+        let package_json_path = project_dir.join("package.json");
+        let modified = fs::metadata(&package_json_path).and_then(|m| m.modified()).ok()?;
+
+        if let Some((cached_mtime, value)) = self.entries.lock().ok()?.get(&package_json_path) {
+            if *cached_mtime == modified {
+                return Some(Arc::clone(value));
+            }
+        }
+
+        let value = Arc::new(read_package_json(project_dir)?);
+        self.entries
+            .lock()
+            .ok()?
+            .insert(package_json_path, (modified, Arc::clone(&value)));
+        Some(value)
+    }
+}
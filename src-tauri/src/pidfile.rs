@@ -0,0 +1,301 @@
+// src-tauri/src/pidfile.rs
+
+use crate::history::{elapsed_ms, RunRecord};
+use crate::runner::{RunEvent, RunTrigger};
+use crate::types::{AppState, RunningProcess};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+const PID_DIR_NAME: &str = ".dld-pids";
+
+/// Intervalle de vérification des processus adoptés.
+const ADOPTED_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Fichier décrivant un processus lancé par l'application, pour le retrouver après un redémarrage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PidFile {
+    pub run_id: String,
+    pub project_id: String,
+    pub script: String,
+    #[serde(default)]
+    pub source: Option<String>,
+    pub manager: String,
+    pub pid: u32,
+    /// Groupe de processus : identique au PID, le script étant lancé dans son propre groupe.
+    pub pgid: u32,
+    pub started_at: String,
+    #[serde(default)]
+    pub trigger: RunTrigger,
+    #[serde(flatten)]
+    pub identity: ProcessIdentity,
+}
+
+/// Caractéristiques distinguant un processus d'un autre ayant réutilisé son PID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessIdentity {
+    /// Instant de démarrage du processus, dans l'unité du système (ticks depuis le démarrage sous Linux).
+    pub start_time: u64,
+    /// Ligne de commande au lancement, à titre indicatif : npm, pnpm et yarn réécrivent
+    /// `process.title`, elle ne permet donc pas de reconnaître le processus.
+    pub command: String,
+}
+
+impl ProcessIdentity {
+    /// Indique si deux identités lues pour un même PID désignent le même processus :
+    /// un PID réutilisé a nécessairement un autre instant de démarrage.
+    pub fn same_process(&self, other: &ProcessIdentity) -> bool {
+        self.start_time == other.start_time
+    }
+}
+
+/// Obtient le dossier des fichiers PID.
+fn get_pid_dir() -> PathBuf {
+    dirs::home_dir()
+        .expect("Impossible de trouver le dossier utilisateur")
+        .join(PID_DIR_NAME)
+}
+
+fn pid_file_path(run_id: &str) -> PathBuf {
+    get_pid_dir().join(format!("{}.json", run_id))
+}
+
+/// Enregistre le fichier PID d'une exécution, à chaque (re)lancement de son processus.
+pub fn write(process: &RunningProcess) {
+    let Some(pid) = process.pid else {
+        return;
+    };
+    let Some(identity) = process_identity(pid) else {
+        warn!("Impossible d'identifier le processus {} du script '{}'", pid, process.script);
+        return;
+    };
+    let pid_file = PidFile {
+        run_id: process.run_id.clone(),
+        project_id: process.project_id.clone(),
+        script: process.script.clone(),
+        source: process.source.clone(),
+        manager: process.manager.clone(),
+        pid,
+        pgid: pid,
+        started_at: process.started_at.clone(),
+        trigger: process.trigger,
+        identity,
+    };
+
+    let result = fs::create_dir_all(get_pid_dir()).and_then(|_| {
+        let contents = serde_json::to_string_pretty(&pid_file).map_err(std::io::Error::from)?;
+        fs::write(pid_file_path(&process.run_id), contents)
+    });
+    if let Err(e) = result {
+        error!("Erreur lors de l'écriture du fichier PID de {}: {}", process.run_id, e);
+    }
+}
+
+/// Supprime le fichier PID d'une exécution terminée.
+pub fn remove(run_id: &str) {
+    let _ = fs::remove_file(pid_file_path(run_id));
+}
+
+/// Retrouve les scripts toujours en cours lancés par une instance précédente de l'application
+/// et les enregistre comme adoptés : ils peuvent être arrêtés, mais leur sortie n'est plus relayée.
+pub fn adopt_orphans<R: Runtime>(app: AppHandle<R>) {
+    let Ok(entries) = fs::read_dir(get_pid_dir()) else {
+        return;
+    };
+    let state = app.state::<AppState>();
+    let mut adopted = Vec::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let pid_file = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<PidFile>(&contents).ok());
+        let Some(pid_file) = pid_file else {
+            let _ = fs::remove_file(&path);
+            continue;
+        };
+        // Un processus terminé, ou un autre ayant réutilisé le PID, n'est pas adopté.
+        if !is_running(&pid_file) {
+            let _ = fs::remove_file(&path);
+            continue;
+        }
+
+        info!(
+            "Script '{}' (PID {}) adopté pour le projet ID '{}'",
+            pid_file.script, pid_file.pid, pid_file.project_id
+        );
+        state.store.register_process(RunningProcess {
+            run_id: pid_file.run_id.clone(),
+            project_id: pid_file.project_id.clone(),
+            script: pid_file.script.clone(),
+            source: pid_file.source.clone(),
+            manager: pid_file.manager.clone(),
+            pid: Some(pid_file.pid),
+            started_at: pid_file.started_at.clone(),
+            trigger: pid_file.trigger,
+            restarts: 0,
+            stop_requested: false,
+            restart_requested: false,
+            health: None,
+            url: None,
+            adopted: true,
+        });
+        adopted.push(pid_file);
+    }
+
+    if !adopted.is_empty() {
+        tauri::async_runtime::spawn(watch_adopted(app, adopted));
+    }
+}
+
+/// Surveille les processus adoptés jusqu'à leur fin, faute de pouvoir attendre leur code de sortie.
+async fn watch_adopted<R: Runtime>(app: AppHandle<R>, mut adopted: Vec<PidFile>) {
+    let state = app.state::<AppState>();
+    while !adopted.is_empty() {
+        tokio::time::sleep(ADOPTED_POLL_INTERVAL).await;
+        let (alive, exited): (Vec<_>, Vec<_>) = adopted
+            .into_iter()
+            .partition(is_running);
+        adopted = alive;
+
+        for pid_file in exited {
+            info!("Script adopté '{}' terminé pour le projet ID '{}'", pid_file.script, pid_file.project_id);
+            state.store.unregister_process(&pid_file.run_id);
+            remove(&pid_file.run_id);
+            // Le code de sortie d'un processus qui n'est pas notre enfant est inconnu : l'exécution
+            // est enregistrée sans code ni signal, et comptée comme un échec.
+            let ended_at = chrono::Local::now();
+            state.history.append(&RunRecord {
+                run_id: pid_file.run_id.clone(),
+                project_id: pid_file.project_id.clone(),
+                script: pid_file.script.clone(),
+                program: pid_file.manager.clone(),
+                args: Vec::new(),
+                trigger: pid_file.trigger,
+                duration_ms: elapsed_ms(&pid_file.started_at, &ended_at),
+                started_at: pid_file.started_at.clone(),
+                ended_at: ended_at.to_rfc3339(),
+                success: false,
+                exit_code: None,
+                signal: None,
+                restarts: 0,
+                limit: None,
+            });
+            if let Err(e) = app.emit("script_finished", json!({ "id": pid_file.project_id, "run_id": pid_file.run_id })) {
+                error!("Erreur lors de l'émission de script_finished: {}", e);
+            }
+            let _ = state.run_events.send(RunEvent::Exited {
+                run_id: pid_file.run_id,
+                success: false,
+                code: None,
+                limit: None,
+            });
+        }
+    }
+}
+
+/// Indique si le processus décrit par un fichier PID est toujours en cours.
+fn is_running(pid_file: &PidFile) -> bool {
+    process_identity(pid_file.pid).is_some_and(|identity| identity.same_process(&pid_file.identity))
+}
+
+/// Lit l'instant de démarrage et la ligne de commande d'un processus dans `/proc`.
+#[cfg(target_os = "linux")]
+pub fn process_identity(pid: u32) -> Option<ProcessIdentity> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let cmdline = fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    parse_process_identity(&stat, &cmdline)
+}
+
+/// Extrait l'identité d'un processus du contenu de `/proc/<pid>/stat` et `/proc/<pid>/cmdline`.
+#[cfg(target_os = "linux")]
+fn parse_process_identity(stat: &str, cmdline: &[u8]) -> Option<ProcessIdentity> {
+    // Le nom du programme, entre parenthèses, peut contenir des espaces : les champs suivants
+    // sont lus après la dernière parenthèse. `starttime` est le 22e champ, le 20e après celle-ci.
+    let fields = stat.get(stat.rfind(')')? + 1..)?;
+    let start_time = fields.split_whitespace().nth(19)?.parse().ok()?;
+    let command = cmdline
+        .split(|byte| *byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(ProcessIdentity { start_time, command })
+}
+
+/// Lit l'instant de démarrage et la ligne de commande d'un processus via `sysinfo`.
+#[cfg(not(target_os = "linux"))]
+pub fn process_identity(pid: u32) -> Option<ProcessIdentity> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::new().with_cmd(UpdateKind::Always),
+    );
+    let process = system.process(pid)?;
+    let command = process
+        .cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(ProcessIdentity {
+        start_time: process.start_time(),
+        command,
+    })
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    const STAT: &str = "4242 (node (dev) server) S 1 4242 4242 0 -1 4194560 1520 0 0 0 12 3 0 0 20 0 11 0 987654 1100000000 12000 18446744073709551615 1 1 0 0 0 0 0 16781312 17922 0 0 0 17 3 0 0 0 0 0\n";
+
+    #[test]
+    fn parses_stat_and_cmdline() {
+        let identity = parse_process_identity(STAT, b"node\0node_modules/.bin/vite\0--open\0").unwrap();
+        assert_eq!(identity.start_time, 987654);
+        assert_eq!(identity.command, "node node_modules/.bin/vite --open");
+    }
+
+    #[test]
+    fn keeps_empty_command_of_kernel_threads() {
+        let identity = parse_process_identity(STAT, b"").unwrap();
+        assert_eq!((identity.start_time, identity.command.as_str()), (987654, ""));
+    }
+
+    #[test]
+    fn rejects_truncated_stat() {
+        assert_eq!(parse_process_identity("4242 (node) S 1 4242", b"node\0"), None);
+        assert_eq!(parse_process_identity("4242 node S", b"node\0"), None);
+        assert_eq!(parse_process_identity("", b""), None);
+    }
+
+    #[test]
+    fn same_process_ignores_rewritten_command() {
+        let launched = parse_process_identity(STAT, b"node\0/usr/bin/pnpm\0run\0dev\0").unwrap();
+        let retitled = parse_process_identity(STAT, b"pnpm run dev\0").unwrap();
+        assert_ne!(launched.command, retitled.command);
+        assert!(launched.same_process(&retitled));
+    }
+
+    #[test]
+    fn reused_pid_is_another_process() {
+        let launched = parse_process_identity(STAT, b"node\0vite\0").unwrap();
+        let reused = ProcessIdentity { start_time: launched.start_time + 1, ..launched.clone() };
+        assert!(!launched.same_process(&reused));
+    }
+
+    #[test]
+    fn reads_own_identity() {
+        let identity = process_identity(std::process::id()).unwrap();
+        assert_eq!(process_identity(std::process::id()), Some(identity));
+    }
+}
@@ -0,0 +1,371 @@
+// src-tauri/src/probes.rs
+
+use crate::readiness::{http_status, tcp_open};
+use crate::runner::{request_restart, RunEvent};
+use crate::types::AppState;
+use log::{error, info, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::process::Command as TokioCommand;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::Instant;
+
+/// Délai maximal d'exécution d'une sonde de type commande.
+const COMMAND_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn default_interval_ms() -> u64 {
+    10_000
+}
+
+fn default_initial_delay_ms() -> u64 {
+    5_000
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+fn default_host() -> String {
+    "localhost".to_string()
+}
+
+/// Sonde vérifiant qu'un script en cours répond toujours.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HealthProbe {
+    /// `GET` sur l'URL donnée, ou sur l'URL affichée par le script à défaut.
+    /// Sans statut attendu, toute réponse 2xx ou 3xx est acceptée.
+    Http {
+        #[serde(default)]
+        url: Option<String>,
+        #[serde(default)]
+        status: Option<u16>,
+    },
+    /// Connexion TCP sur le port donné.
+    Tcp {
+        port: u16,
+        #[serde(default = "default_host")]
+        host: String,
+    },
+    /// Commande exécutée dans le dossier du projet ; un code de sortie nul indique un script sain.
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+/// Vérification périodique de l'état d'un script.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheck {
+    pub probe: HealthProbe,
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+    #[serde(default = "default_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Redémarre le script lorsqu'il devient défaillant.
+    #[serde(default)]
+    pub restart_on_failure: bool,
+}
+
+/// État de santé d'un script en cours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptHealth {
+    Starting,
+    Healthy,
+    Unhealthy,
+}
+
+/// Extrait l'URL locale affichée par un serveur de développement (`Local: http://localhost:5173/`).
+pub fn detect_local_url(line: &str) -> Option<String> {
+    static ANSI: OnceLock<Regex> = OnceLock::new();
+    static URL: OnceLock<Regex> = OnceLock::new();
+    let ansi = ANSI.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").expect("regex ANSI invalide"));
+    let url = URL.get_or_init(|| {
+        Regex::new(r"https?://(localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1?\])(:\d+)?[^\s]*").expect("regex URL invalide")
+    });
+
+    let line = ansi.replace_all(line, "");
+    url.find(&line)
+        .map(|found| found.as_str().replace("0.0.0.0", "localhost").replace("[::]", "localhost"))
+}
+
+/// Surveille l'état de santé d'une exécution jusqu'à sa fin.
+/// `events` doit être abonné avant le début de la sortie pour ne pas manquer l'URL affichée.
+pub async fn monitor<R: Runtime>(
+    app: AppHandle<R>,
+    run_id: String,
+    check: HealthCheck,
+    mut events: broadcast::Receiver<RunEvent>,
+) {
+    let state = app.state::<AppState>();
+    let Some(process) = state.store.process(&run_id) else {
+        return;
+    };
+    let Some(cwd) = state.store.project(&process.project_id).map(|project| project.path) else {
+        return;
+    };
+
+    let mut health = ScriptHealth::Starting;
+    let mut failures = 0;
+    let mut ticks = tokio::time::interval_at(
+        Instant::now() + Duration::from_millis(check.initial_delay_ms),
+        Duration::from_millis(check.interval_ms.max(500)),
+    );
+    state.store.update_process(&run_id, |process| process.health = Some(health));
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(RunEvent::Output { run_id: id, line }) if id == run_id => {
+                    if let Some(url) = detect_local_url(&line) {
+                        state.store.update_process(&run_id, |process| {
+                            process.url.get_or_insert(url);
+                        });
+                    }
+                }
+                Ok(RunEvent::Exited { run_id: id, .. }) if id == run_id => return,
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return,
+            },
+            _ = ticks.tick() => {
+                let Some(process) = state.store.process(&run_id) else {
+                    return;
+                };
+                // Processus en attente de redémarrage : rien à sonder.
+                if process.pid.is_none() {
+                    continue;
+                }
+
+                let result = run_probe(&check.probe, process.url.as_deref(), Path::new(&cwd)).await;
+                let error = result.err();
+                let next = next_health(health, &mut failures, error.is_none(), check.failure_threshold);
+                if next == health {
+                    continue;
+                }
+
+                health = next;
+                state.store.update_process(&run_id, |process| process.health = Some(health));
+                let payload = json!({
+                    "id": process.project_id,
+                    "run_id": run_id,
+                    "health": health,
+                    "failures": failures,
+                    "error": error,
+                });
+                if let Err(e) = app.emit("script_health", payload) {
+                    error!("Erreur lors de l'émission de script_health: {}", e);
+                }
+
+                if health == ScriptHealth::Unhealthy {
+                    warn!("Script '{}' défaillant pour le projet ID '{}'", process.script, process.project_id);
+                    if check.restart_on_failure {
+                        info!("Redémarrage du script '{}' suite à l'échec de la sonde", process.script);
+                        if let Err(e) = request_restart(&app, &run_id) {
+                            error!("Impossible de redémarrer le script : {}", e);
+                        }
+                        failures = 0;
+                        health = ScriptHealth::Starting;
+                        state.store.update_process(&run_id, |process| process.health = Some(health));
+                        ticks.reset_after(Duration::from_millis(check.initial_delay_ms));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// État de santé après une sonde : sain dès qu'elle réussit, défaillant après
+/// `failure_threshold` échecs consécutifs, inchangé entre les deux.
+fn next_health(health: ScriptHealth, failures: &mut u32, succeeded: bool, failure_threshold: u32) -> ScriptHealth {
+    if succeeded {
+        *failures = 0;
+        return ScriptHealth::Healthy;
+    }
+    *failures += 1;
+    if *failures >= failure_threshold {
+        ScriptHealth::Unhealthy
+    } else {
+        health
+    }
+}
+
+/// Exécute une sonde et retourne l'erreur rencontrée si le script ne répond pas.
+async fn run_probe(probe: &HealthProbe, detected_url: Option<&str>, cwd: &Path) -> Result<(), String> {
+    match probe {
+        HealthProbe::Http { url, status } => {
+            let url = url
+                .as_deref()
+                .or(detected_url)
+                .ok_or_else(|| "Aucune URL détectée pour le script.".to_string())?;
+
+            // Sans client TLS, une URL https est vérifiée par une connexion TCP sur son port.
+            if let Some(rest) = url.strip_prefix("https://") {
+                let authority = rest.split('/').next().unwrap_or(rest);
+                let (host, port) = authority.rsplit_once(':').unwrap_or((authority, "443"));
+                let port = port.parse().map_err(|_| format!("Port invalide : {}", url))?;
+                return if tcp_open(host, port).await {
+                    Ok(())
+                } else {
+                    Err(format!("{} ne répond pas.", url))
+                };
+            }
+
+            let received = http_status(url).await?;
+            let expected = match status {
+                Some(status) => received == *status,
+                None => (200..400).contains(&received),
+            };
+            if expected {
+                Ok(())
+            } else {
+                Err(format!("{} a répondu {}.", url, received))
+            }
+        }
+        HealthProbe::Tcp { port, host } => {
+            if tcp_open(host, *port).await {
+                Ok(())
+            } else {
+                Err(format!("Le port {} ne répond pas.", port))
+            }
+        }
+        HealthProbe::Command { program, args } => {
+            let output = TokioCommand::new(program)
+                .args(args)
+                .current_dir(cwd)
+                .kill_on_drop(true)
+                .output();
+            match tokio::time::timeout(COMMAND_PROBE_TIMEOUT, output).await {
+                Ok(Ok(output)) if output.status.success() => Ok(()),
+                Ok(Ok(output)) => Err(format!(
+                    "La sonde a terminé avec le code {}.",
+                    output.status.code().unwrap_or(-1)
+                )),
+                Ok(Err(e)) => Err(e.to_string()),
+                Err(_) => Err("Délai de la sonde dépassé.".to_string()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn detects_local_urls() {
+        assert_eq!(
+            detect_local_url("  \x1b[32m➜\x1b[39m  \x1b[1mLocal\x1b[22m:   \x1b[36mhttp://localhost:\x1b[1m5173\x1b[22m/\x1b[39m"),
+            Some("http://localhost:5173/".to_string())
+        );
+        assert_eq!(
+            detect_local_url("Listening on http://0.0.0.0:3000"),
+            Some("http://localhost:3000".to_string())
+        );
+        assert_eq!(
+            detect_local_url("ready on http://[::]:8080/app"),
+            Some("http://localhost:8080/app".to_string())
+        );
+        assert_eq!(detect_local_url("- Local: http://127.0.0.1:4321"), Some("http://127.0.0.1:4321".to_string()));
+        assert_eq!(detect_local_url("Docs: https://vitejs.dev/config"), None);
+        assert_eq!(detect_local_url("compiled successfully"), None);
+    }
+
+    #[test]
+    fn becomes_unhealthy_at_failure_threshold() {
+        let mut failures = 0;
+        let mut health = ScriptHealth::Starting;
+        health = next_health(health, &mut failures, true, 3);
+        assert_eq!(health, ScriptHealth::Healthy);
+        health = next_health(health, &mut failures, false, 3);
+        health = next_health(health, &mut failures, false, 3);
+        assert_eq!((health, failures), (ScriptHealth::Healthy, 2));
+        health = next_health(health, &mut failures, false, 3);
+        assert_eq!((health, failures), (ScriptHealth::Unhealthy, 3));
+        health = next_health(health, &mut failures, true, 3);
+        assert_eq!((health, failures), (ScriptHealth::Healthy, 0));
+    }
+
+    /// Serveur local répondant 200 à la première requête, puis 500.
+    async fn flaky_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/health", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut requests = 0;
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let _ = socket.read(&mut request).await;
+                let status = if requests == 0 { "200 OK" } else { "500 Internal Server Error" };
+                let _ = socket
+                    .write_all(format!("HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status).as_bytes())
+                    .await;
+                requests += 1;
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn http_probe_goes_from_healthy_to_unhealthy() {
+        let url = flaky_server().await;
+        let probe = HealthProbe::Http { url: None, status: None };
+        let cwd = std::env::temp_dir();
+        let threshold = 2;
+
+        let mut failures = 0;
+        let mut health = ScriptHealth::Starting;
+        let mut history = Vec::new();
+        for _ in 0..3 {
+            let result = run_probe(&probe, Some(&url), &cwd).await;
+            health = next_health(health, &mut failures, result.is_ok(), threshold);
+            history.push(health);
+        }
+        assert_eq!(history, [ScriptHealth::Healthy, ScriptHealth::Healthy, ScriptHealth::Unhealthy]);
+        assert_eq!(
+            run_probe(&probe, Some(&url), &cwd).await,
+            Err(format!("{} a répondu 500.", url))
+        );
+        assert!(run_probe(&probe, None, &cwd).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn tcp_probe_checks_the_port() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let cwd = std::env::temp_dir();
+        let probe = HealthProbe::Tcp { port, host: "127.0.0.1".to_string() };
+        assert_eq!(run_probe(&probe, None, &cwd).await, Ok(()));
+
+        drop(listener);
+        assert_eq!(run_probe(&probe, None, &cwd).await, Err(format!("Le port {} ne répond pas.", port)));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_probe_uses_exit_code_and_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("healthy"), "").unwrap();
+        let probe = |file: &str| HealthProbe::Command {
+            program: "test".to_string(),
+            args: vec!["-f".to_string(), file.to_string()],
+        };
+        assert_eq!(run_probe(&probe("healthy"), None, dir.path()).await, Ok(()));
+        assert_eq!(
+            run_probe(&probe("missing"), None, dir.path()).await,
+            Err("La sonde a terminé avec le code 1.".to_string())
+        );
+
+        let missing = HealthProbe::Command { program: "dld-missing-probe".to_string(), args: Vec::new() };
+        assert!(run_probe(&missing, None, dir.path()).await.is_err());
+    }
+}
@@ -0,0 +1,154 @@
+// src-tauri/src/profiles.rs
+
+use crate::framework::detect_ready_pattern;
+use crate::readiness::{ReadinessCheck, DEFAULT_READINESS_TIMEOUT};
+use crate::runner::{LaunchRequest, RunTrigger};
+use crate::script::find_task;
+use crate::types::{ProjectConfig, RunningProcess};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Ordre de démarrage des scripts d'un profil.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileMode {
+    /// Tous les scripts sont lancés en même temps.
+    #[default]
+    Parallel,
+    /// Chaque script est lancé une fois le précédent prêt (condition de disponibilité de ses réglages,
+    /// ou motif de son framework ; à défaut, dès son lancement) ; un échec interrompt la suite.
+    Sequential,
+}
+
+/// Script à lancer dans le cadre d'un profil.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileEntry {
+    pub project_id: String,
+    pub script: String,
+    /// Source de la tâche (`package.json`, `Makefile`, ...) ; la première tâche du même nom sinon.
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// Profil de lancement nommé regroupant des scripts de plusieurs projets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub mode: ProfileMode,
+    pub entries: Vec<ProfileEntry>,
+}
+
+/// État d'un script de profil.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryState {
+    Running,
+    /// En file d'attente, les limites de concurrence étant atteintes.
+    Queued,
+    Stopped,
+    Failed,
+    Skipped,
+}
+
+/// État d'un script de profil et exécution correspondante.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileEntryStatus {
+    pub project_id: String,
+    pub script: String,
+    pub state: EntryState,
+    pub run_id: Option<String>,
+    pub error: Option<String>,
+}
+
+/// État agrégé d'un profil.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileStatus {
+    pub profile_id: String,
+    pub running: usize,
+    pub total: usize,
+    pub entries: Vec<ProfileEntryStatus>,
+}
+
+impl ProfileStatus {
+    pub fn new(profile_id: &str, entries: Vec<ProfileEntryStatus>) -> Self {
+        ProfileStatus {
+            profile_id: profile_id.to_string(),
+            running: entries.iter().filter(|entry| entry.state == EntryState::Running).count(),
+            total: entries.len(),
+            entries,
+        }
+    }
+}
+
+impl ProfileEntryStatus {
+    pub fn new(entry: &ProfileEntry, state: EntryState, run_id: Option<String>, error: Option<String>) -> Self {
+        ProfileEntryStatus {
+            project_id: entry.project_id.clone(),
+            script: entry.script.clone(),
+            state,
+            run_id,
+            error,
+        }
+    }
+}
+
+/// Construit la demande de lancement d'un script de profil à partir des tâches du projet.
+pub fn resolve_entry(project: &ProjectConfig, entry: &ProfileEntry) -> Result<LaunchRequest, String> {
+    let path = PathBuf::from(&project.path);
+    let task = find_task(&path, entry.source.as_deref(), &entry.script)
+        .ok_or_else(|| format!("Tâche {} introuvable pour {}.", entry.script, project.name))?;
+
+    let mut request = LaunchRequest::for_task(&project.id, task, path);
+    if !entry.args.is_empty() {
+        // npm ne transmet les arguments au script qu'après `--`.
+        if request.program == "npm" {
+            request.args.push("--".to_string());
+        }
+        request.args.extend(entry.args.iter().cloned());
+    }
+    request.env = entry.env.clone();
+    request.trigger = RunTrigger::Profile;
+    Ok(request)
+}
+
+/// Condition de disponibilité d'un script de profil et délai d'attente, pour le mode séquentiel :
+/// celle de ses réglages, sinon le motif de disponibilité de son framework, s'il en a un.
+pub fn entry_readiness(project: &ProjectConfig, entry: &ProfileEntry) -> Option<(ReadinessCheck, Duration)> {
+    let settings = project.settings_for(entry.source.as_deref(), &entry.script);
+    let check = settings.and_then(|settings| settings.readiness.clone()).or_else(|| {
+        detect_ready_pattern(Path::new(&project.path)).map(|pattern| ReadinessCheck::Output { pattern })
+    })?;
+    let timeout = settings
+        .and_then(|settings| settings.readiness_timeout_secs)
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_READINESS_TIMEOUT);
+    Some((check, timeout))
+}
+
+/// Retrouve l'exécution en cours correspondant à un script de profil.
+pub fn find_running<'a>(processes: &'a [RunningProcess], entry: &ProfileEntry) -> Option<&'a RunningProcess> {
+    processes
+        .iter()
+        .find(|process| process.runs(&entry.project_id, entry.source.as_deref(), &entry.script))
+}
+
+/// Calcule l'état d'un profil à partir des processus en cours.
+pub fn profile_status(profile: &LaunchProfile, processes: &[RunningProcess]) -> ProfileStatus {
+    let entries = profile
+        .entries
+        .iter()
+        .map(|entry| match find_running(processes, entry) {
+            Some(process) => ProfileEntryStatus::new(entry, EntryState::Running, Some(process.run_id.clone()), None),
+            None => ProfileEntryStatus::new(entry, EntryState::Stopped, None, None),
+        })
+        .collect();
+    ProfileStatus::new(&profile.id, entries)
+}
@@ -0,0 +1,318 @@
+// src-tauri/src/project_type.rs
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Type de projet, déterminé par son fichier manifeste.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectType {
+    #[default]
+    Node,
+    Cargo,
+    Python,
+    Go,
+    Php,
+    Ruby,
+}
+
+/// Types de projet et fichiers manifestes permettant de les reconnaître, par ordre de priorité.
+const PROJECT_MANIFESTS: &[(ProjectType, &[&str])] = &[
+    (ProjectType::Node, &["package.json"]),
+    (ProjectType::Cargo, &["Cargo.toml"]),
+    (ProjectType::Python, &["pyproject.toml", "requirements.txt", "manage.py"]),
+    (ProjectType::Go, &["go.mod"]),
+    (ProjectType::Php, &["composer.json"]),
+    (ProjectType::Ruby, &["Gemfile", "Rakefile"]),
+];
+
+/// Dépendances déclarées dans le manifeste d'un projet.
+#[derive(Debug, Clone, Default)]
+pub struct ManifestDependencies {
+    pub dependencies: Vec<String>,
+    pub dev_dependencies: Vec<String>,
+}
+
+/// Retourne tous les fichiers manifestes reconnus.
+pub fn manifest_file_names() -> impl Iterator<Item = &'static str> {
+    PROJECT_MANIFESTS.iter().flat_map(|(_, files)| files.iter().copied())
+}
+
+/// Détecte les types d'un projet à partir des manifestes présents dans son dossier.
+pub fn detect_project_types(project_dir: &Path) -> Vec<ProjectType> {
+    PROJECT_MANIFESTS
+        .iter()
+        .filter(|(_, files)| files.iter().any(|file| project_dir.join(file).is_file()))
+        .map(|(project_type, _)| *project_type)
+        .collect()
+}
+
+/// Indique si le dossier contient au moins un manifeste reconnu.
+pub fn has_manifest(project_dir: &Path) -> bool {
+    !detect_project_types(project_dir).is_empty()
+}
+
+/// Lit un fichier TOML du projet.
+pub fn read_toml(path: &Path) -> Option<toml::Value> {
+    toml::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Lit les dépendances déclarées par le manifeste d'un type de projet.
+pub fn read_dependencies(
+    project_dir: &Path,
+    project_type: ProjectType,
+    package_json: Option<&Value>,
+) -> ManifestDependencies {
+    match project_type {
+        ProjectType::Node => ManifestDependencies {
+            dependencies: json_keys(package_json, "dependencies"),
+            dev_dependencies: json_keys(package_json, "devDependencies"),
+        },
+        ProjectType::Cargo => cargo_dependencies(project_dir),
+        ProjectType::Python => python_dependencies(project_dir),
+        ProjectType::Go => go_dependencies(project_dir),
+        ProjectType::Php => {
+            let composer_json = fs::read_to_string(project_dir.join("composer.json"))
+                .ok()
+                .and_then(|contents| serde_json::from_str::<Value>(&contents).ok());
+            ManifestDependencies {
+                dependencies: json_keys(composer_json.as_ref(), "require"),
+                dev_dependencies: json_keys(composer_json.as_ref(), "require-dev"),
+            }
+        }
+        ProjectType::Ruby => ruby_dependencies(project_dir),
+    }
+}
+
+/// Clés d'un objet JSON (ex. `dependencies` de package.json).
+fn json_keys(document: Option<&Value>, field: &str) -> Vec<String> {
+    document
+        .and_then(|document| document.get(field))
+        .and_then(Value::as_object)
+        .map(|object| object.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Clés d'une table TOML (ex. `[dependencies]` de Cargo.toml).
+fn toml_keys(document: Option<&toml::Value>, path: &[&str]) -> Vec<String> {
+    path.iter()
+        .try_fold(document, |current, key| current.map(|value| value.get(key)))
+        .flatten()
+        .and_then(toml::Value::as_table)
+        .map(|table| table.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn cargo_dependencies(project_dir: &Path) -> ManifestDependencies {
+    let cargo_toml = read_toml(&project_dir.join("Cargo.toml"));
+    let mut dependencies = toml_keys(cargo_toml.as_ref(), &["dependencies"]);
+    dependencies.extend(toml_keys(cargo_toml.as_ref(), &["workspace", "dependencies"]));
+
+    ManifestDependencies {
+        dependencies,
+        dev_dependencies: toml_keys(cargo_toml.as_ref(), &["dev-dependencies"]),
+    }
+}
+
+fn python_dependencies(project_dir: &Path) -> ManifestDependencies {
+    let pyproject = read_toml(&project_dir.join("pyproject.toml"));
+
+    // PEP 621 : `[project] dependencies = ["django>=4.2", ...]`
+    let mut dependencies: Vec<String> = pyproject
+        .as_ref()
+        .and_then(|pyproject| pyproject.get("project")?.get("dependencies")?.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(toml::Value::as_str)
+        .filter_map(requirement_name)
+        .collect();
+
+    // Poetry : `[tool.poetry.dependencies]`
+    dependencies.extend(
+        toml_keys(pyproject.as_ref(), &["tool", "poetry", "dependencies"])
+            .into_iter()
+            .filter(|name| name != "python"),
+    );
+
+    if let Ok(requirements) = fs::read_to_string(project_dir.join("requirements.txt")) {
+        dependencies.extend(requirements.lines().filter_map(requirement_name));
+    }
+
+    let mut dev_dependencies = toml_keys(pyproject.as_ref(), &["tool", "poetry", "group", "dev", "dependencies"]);
+    dev_dependencies.extend(toml_keys(pyproject.as_ref(), &["tool", "poetry", "dev-dependencies"]));
+
+    ManifestDependencies {
+        dependencies: dependencies.into_iter().map(|name| name.to_lowercase()).collect(),
+        dev_dependencies,
+    }
+}
+
+/// Extrait le nom d'un paquet d'une exigence PEP 508 (`fastapi[all]>=0.110`).
+fn requirement_name(requirement: &str) -> Option<String> {
+    let requirement = requirement.trim();
+    if requirement.is_empty() || requirement.starts_with(['#', '-']) {
+        return None;
+    }
+
+    let name: String = requirement
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    Some(name).filter(|name| !name.is_empty())
+}
+
+fn go_dependencies(project_dir: &Path) -> ManifestDependencies {
+    let contents = fs::read_to_string(project_dir.join("go.mod")).unwrap_or_default();
+    let mut dependencies = Vec::new();
+    let mut in_require_block = false;
+
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with("require (") {
+            in_require_block = true;
+        } else if in_require_block && line == ")" {
+            in_require_block = false;
+        } else if let Some(module) = line.strip_prefix("require ") {
+            dependencies.extend(module.split_whitespace().next().map(str::to_string));
+        } else if in_require_block {
+            dependencies.extend(line.split_whitespace().next().map(str::to_string));
+        }
+    }
+
+    ManifestDependencies {
+        dependencies,
+        dev_dependencies: Vec::new(),
+    }
+}
+
+fn ruby_dependencies(project_dir: &Path) -> ManifestDependencies {
+    let contents = fs::read_to_string(project_dir.join("Gemfile")).unwrap_or_default();
+    let dependencies = contents
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("gem "))
+        .filter_map(|rest| {
+            rest.trim()
+                .split(',')
+                .next()
+                .map(|name| name.trim().trim_matches(|c| c == '\'' || c == '"').to_string())
+        })
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    ManifestDependencies {
+        dependencies,
+        dev_dependencies: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn project_with(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, contents) in files {
+            fs::write(dir.path().join(name), contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn detects_project_types_in_priority_order() {
+        let dir = project_with(&[("Gemfile", ""), ("package.json", "{}"), ("requirements.txt", ""), ("Cargo.toml", "")]);
+        assert_eq!(
+            detect_project_types(dir.path()),
+            [ProjectType::Node, ProjectType::Cargo, ProjectType::Python, ProjectType::Ruby]
+        );
+        assert!(has_manifest(dir.path()));
+    }
+
+    #[test]
+    fn detects_every_manifest() {
+        for (file, project_type) in [
+            ("manage.py", ProjectType::Python),
+            ("go.mod", ProjectType::Go),
+            ("composer.json", ProjectType::Php),
+            ("Rakefile", ProjectType::Ruby),
+        ] {
+            let dir = project_with(&[(file, "")]);
+            assert_eq!(detect_project_types(dir.path()), [project_type], "{}", file);
+        }
+    }
+
+    #[test]
+    fn no_manifest_without_known_files() {
+        let dir = project_with(&[("README.md", "")]);
+        assert!(detect_project_types(dir.path()).is_empty());
+        assert!(!has_manifest(dir.path()));
+    }
+
+    #[test]
+    fn reads_node_dependencies_from_package_json() {
+        let dir = project_with(&[]);
+        let package_json = json!({ "dependencies": { "react": "^18.2.0" }, "devDependencies": { "vite": "^5.0.0" } });
+        let dependencies = read_dependencies(dir.path(), ProjectType::Node, Some(&package_json));
+        assert_eq!(dependencies.dependencies, ["react"]);
+        assert_eq!(dependencies.dev_dependencies, ["vite"]);
+    }
+
+    #[test]
+    fn reads_cargo_dependencies() {
+        let dir = project_with(&[(
+            "Cargo.toml",
+            "[dependencies]\naxum = \"0.7\"\n\n[dev-dependencies]\ntempfile = \"3\"\n\n[workspace.dependencies]\ntokio = \"1\"\n",
+        )]);
+        let dependencies = read_dependencies(dir.path(), ProjectType::Cargo, None);
+        assert_eq!(dependencies.dependencies, ["axum", "tokio"]);
+        assert_eq!(dependencies.dev_dependencies, ["tempfile"]);
+    }
+
+    #[test]
+    fn reads_python_dependencies() {
+        let dir = project_with(&[
+            (
+                "pyproject.toml",
+                "[project]\ndependencies = [\"FastAPI[all]>=0.110\"]\n\n\
+                 [tool.poetry.dependencies]\npython = \"^3.11\"\nDjango = \"^5.0\"\n\n\
+                 [tool.poetry.group.dev.dependencies]\npytest = \"^8.0\"\n",
+            ),
+            ("requirements.txt", "# serveur\nuvicorn==0.29\n-r base.txt\n"),
+        ]);
+        let dependencies = read_dependencies(dir.path(), ProjectType::Python, None);
+        assert_eq!(dependencies.dependencies, ["fastapi", "django", "uvicorn"]);
+        assert_eq!(dependencies.dev_dependencies, ["pytest"]);
+    }
+
+    #[test]
+    fn reads_go_dependencies() {
+        let dir = project_with(&[(
+            "go.mod",
+            "module example.com/demo\n\ngo 1.22\n\nrequire github.com/gin-gonic/gin v1.9.1\n\nrequire (\n\tgithub.com/spf13/cobra v1.8.0\n\tgolang.org/x/sync v0.6.0 // indirect\n)\n",
+        )]);
+        let dependencies = read_dependencies(dir.path(), ProjectType::Go, None);
+        assert_eq!(
+            dependencies.dependencies,
+            ["github.com/gin-gonic/gin", "github.com/spf13/cobra", "golang.org/x/sync"]
+        );
+    }
+
+    #[test]
+    fn reads_composer_dependencies() {
+        let dir = project_with(&[(
+            "composer.json",
+            r#"{ "require": { "laravel/framework": "^11.0" }, "require-dev": { "phpunit/phpunit": "^11.0" } }"#,
+        )]);
+        let dependencies = read_dependencies(dir.path(), ProjectType::Php, None);
+        assert_eq!(dependencies.dependencies, ["laravel/framework"]);
+        assert_eq!(dependencies.dev_dependencies, ["phpunit/phpunit"]);
+    }
+
+    #[test]
+    fn reads_gemfile_dependencies() {
+        let dir = project_with(&[("Gemfile", "source \"https://rubygems.org\"\n\ngem \"rails\", \"~> 7.1\"\ngem 'puma'\n")]);
+        let dependencies = read_dependencies(dir.path(), ProjectType::Ruby, None);
+        assert_eq!(dependencies.dependencies, ["rails", "puma"]);
+    }
+}
@@ -0,0 +1,334 @@
+// src-tauri/src/queue.rs
+
+use crate::runner::{launch, LaunchRequest, RunEvent};
+use crate::types::AppState;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::oneshot;
+
+/// Nombre maximal de scripts exécutés simultanément.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConcurrencyLimits {
+    /// Limite globale, tous projets confondus.
+    #[serde(default)]
+    pub max_running: Option<usize>,
+    /// Limite par étiquette de script (`build`, `test`, ...).
+    #[serde(default)]
+    pub per_tag: HashMap<String, usize>,
+}
+
+impl ConcurrencyLimits {
+    /// Indique si un script portant ces étiquettes peut démarrer à côté des scripts en cours.
+    fn allows(&self, running: &[Vec<String>], tags: &[String]) -> bool {
+        if self.max_running.is_some_and(|max| running.len() >= max) {
+            return false;
+        }
+        tags.iter().all(|tag| match self.per_tag.get(tag) {
+            Some(max) => running.iter().filter(|running_tags| running_tags.contains(tag)).count() < *max,
+            None => true,
+        })
+    }
+}
+
+/// Demande de lancement en attente d'une place libre.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueuedRun {
+    pub queue_id: String,
+    pub project_id: String,
+    pub script: String,
+    pub tags: Vec<String>,
+    pub queued_at: String,
+    #[serde(skip)]
+    request: LaunchRequest,
+}
+
+/// Résultat d'une demande de lancement.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Submission {
+    Started { run_id: String },
+    Queued { queue_id: String, position: usize },
+}
+
+/// File des lancements en attente, servie dans l'ordre dès que les limites le permettent.
+#[derive(Default)]
+pub struct RunQueue {
+    entries: Mutex<Vec<QueuedRun>>,
+    /// Demandeurs attendant le démarrage effectif d'un lancement mis en file (`submit_and_wait`).
+    waiters: Mutex<HashMap<String, oneshot::Sender<Result<String, String>>>>,
+    /// Sérialise les lancements pour que chaque décision tienne compte des scripts qui viennent de démarrer.
+    dispatching: tokio::sync::Mutex<()>,
+}
+
+impl RunQueue {
+    /// Retourne les lancements en attente, dans l'ordre.
+    pub fn entries(&self) -> Vec<QueuedRun> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Retire un lancement de la file ; un demandeur qui attendait son démarrage reçoit une erreur.
+    pub fn cancel(&self, queue_id: &str) -> Option<QueuedRun> {
+        self.waiters.lock().unwrap_or_else(PoisonError::into_inner).remove(queue_id);
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = entries.iter().position(|entry| entry.queue_id == queue_id)?;
+        Some(entries.remove(index))
+    }
+
+    /// Déplace un lancement à la position donnée (0 pour le premier), bornée à la fin de la file.
+    pub fn reorder(&self, queue_id: &str, position: usize) -> Result<(), String> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = entries
+            .iter()
+            .position(|entry| entry.queue_id == queue_id)
+            .ok_or_else(|| format!("Lancement {} introuvable dans la file.", queue_id))?;
+        let entry = entries.remove(index);
+        let position = position.min(entries.len());
+        entries.insert(position, entry);
+        Ok(())
+    }
+
+    fn position(&self, queue_id: &str) -> Option<usize> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .position(|entry| entry.queue_id == queue_id)
+    }
+
+    /// Retire le premier lancement autorisé par les limites ; un lancement bloqué par une
+    /// étiquette saturée ne retient pas ceux qui le suivent.
+    fn take_next(&self, limits: &ConcurrencyLimits, running: &[Vec<String>]) -> Option<QueuedRun> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = entries.iter().position(|entry| limits.allows(running, &entry.tags))?;
+        Some(entries.remove(index))
+    }
+}
+
+/// Étiquettes configurées pour un script.
+fn script_tags(state: &AppState, project_id: &str, source: Option<&str>, script: &str) -> Vec<String> {
+    state
+        .store
+        .project(project_id)
+        .and_then(|project| project.settings_for(source, script).map(|settings| settings.tags.clone()))
+        .unwrap_or_default()
+}
+
+/// Lance un script immédiatement si les limites le permettent, le place dans la file sinon (`script_queued`).
+pub async fn submit<R: Runtime>(app: AppHandle<R>, request: LaunchRequest) -> Result<Submission, String> {
+    enqueue(app, request, None).await
+}
+
+/// Comme `submit`, mais attend le démarrage effectif d'un lancement mis en file et retourne
+/// son identifiant d'exécution (graphes, profils séquentiels).
+pub async fn submit_and_wait<R: Runtime>(app: AppHandle<R>, request: LaunchRequest) -> Result<String, String> {
+    let (started_tx, started_rx) = oneshot::channel();
+    match enqueue(app, request, Some(started_tx)).await? {
+        Submission::Started { run_id } => Ok(run_id),
+        Submission::Queued { .. } => started_rx
+            .await
+            .map_err(|_| "Lancement en attente annulé.".to_string())?,
+    }
+}
+
+async fn enqueue<R: Runtime>(
+    app: AppHandle<R>,
+    request: LaunchRequest,
+    waiter: Option<oneshot::Sender<Result<String, String>>>,
+) -> Result<Submission, String> {
+    let state = app.state::<AppState>();
+    let queue_id = uuid::Uuid::new_v4().to_string();
+    let entry = QueuedRun {
+        queue_id: queue_id.clone(),
+        project_id: request.project_id.clone(),
+        script: request.script.clone(),
+        tags: script_tags(&state, &request.project_id, request.source.as_deref(), &request.script),
+        queued_at: chrono::Local::now().to_rfc3339(),
+        request,
+    };
+    let (project_id, script) = (entry.project_id.clone(), entry.script.clone());
+    if let Some(waiter) = waiter {
+        state
+            .run_queue
+            .waiters
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(queue_id.clone(), waiter);
+    }
+    state.run_queue.entries.lock().unwrap_or_else(PoisonError::into_inner).push(entry);
+
+    // Le lancement soumis peut démarrer tout de suite, ou un autre plus ancien à sa place.
+    if let Some(result) = dispatch(&app).await.remove(&queue_id) {
+        return result.map(|run_id| Submission::Started { run_id });
+    }
+
+    let position = state.run_queue.position(&queue_id).unwrap_or_default();
+    info!("Script '{}' mis en file d'attente (position {}) pour le projet ID '{}'", script, position, project_id);
+    let payload = json!({
+        "queue_id": queue_id,
+        "id": project_id,
+        "script": script,
+        "position": position,
+    });
+    if let Err(e) = app.emit("script_queued", payload) {
+        error!("Erreur lors de l'émission de script_queued: {}", e);
+    }
+    emit_queue(&app);
+    Ok(Submission::Queued { queue_id, position })
+}
+
+/// Lance les scripts en attente tant que les limites le permettent.
+/// Retourne le résultat de chaque lancement, indexé par identifiant de file.
+pub async fn dispatch<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, Result<String, String>> {
+    let state = app.state::<AppState>();
+    let _dispatching = state.run_queue.dispatching.lock().await;
+    let mut results = HashMap::new();
+
+    loop {
+        let limits = state.store.concurrency();
+        let running: Vec<Vec<String>> = state
+            .store
+            .processes()
+            .iter()
+            .map(|process| script_tags(&state, &process.project_id, process.source.as_deref(), &process.script))
+            .collect();
+        let Some(entry) = state.run_queue.take_next(&limits, &running) else {
+            break;
+        };
+
+        let result = launch(app.clone(), entry.request).await;
+        if let Err(e) = &result {
+            warn!("Échec du lancement en attente du script '{}' : {}", entry.script, e);
+        }
+        let waiter = state
+            .run_queue
+            .waiters
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&entry.queue_id);
+        if let Some(waiter) = waiter {
+            let _ = waiter.send(result.clone());
+        }
+        results.insert(entry.queue_id, result);
+    }
+
+    if !results.is_empty() {
+        emit_queue(app);
+    }
+    results
+}
+
+/// Diffuse l'état de la file (`run_queue`).
+pub fn emit_queue<R: Runtime>(app: &AppHandle<R>) {
+    let entries = app.state::<AppState>().run_queue.entries();
+    if let Err(e) = app.emit("run_queue", entries) {
+        error!("Erreur lors de l'émission de run_queue: {}", e);
+    }
+}
+
+/// Démarre la tâche servant la file à chaque fin d'exécution.
+pub fn start<R: Runtime>(app: AppHandle<R>) {
+    let mut events = app.state::<AppState>().run_events.subscribe();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(RunEvent::Exited { .. }) | Err(RecvError::Lagged(_)) => {
+                    dispatch(&app).await;
+                }
+                Ok(RunEvent::Output { .. }) => {}
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    fn limits(max_running: Option<usize>, per_tag: &[(&str, usize)]) -> ConcurrencyLimits {
+        ConcurrencyLimits {
+            max_running,
+            per_tag: per_tag.iter().map(|(tag, max)| (tag.to_string(), *max)).collect(),
+        }
+    }
+
+    fn queued(queue_id: &str, script_tags: &[&str]) -> QueuedRun {
+        QueuedRun {
+            queue_id: queue_id.to_string(),
+            project_id: "project".to_string(),
+            script: queue_id.to_string(),
+            tags: tags(script_tags),
+            queued_at: String::new(),
+            request: LaunchRequest::for_task(
+                "project",
+                crate::types::Task {
+                    name: queue_id.to_string(),
+                    source: "package.json".to_string(),
+                    command: String::new(),
+                    program: "npm".to_string(),
+                    args: Vec::new(),
+                    description: None,
+                },
+                std::path::PathBuf::new(),
+            ),
+        }
+    }
+
+    #[test]
+    fn allows_within_limits() {
+        let running = [tags(&["build"]), tags(&[])];
+        assert!(ConcurrencyLimits::default().allows(&running, &tags(&["build"])));
+        assert!(limits(Some(3), &[]).allows(&running, &tags(&[])));
+        assert!(!limits(Some(2), &[]).allows(&running, &tags(&[])));
+
+        let per_tag = limits(None, &[("build", 1), ("test", 2)]);
+        assert!(!per_tag.allows(&running, &tags(&["build"])));
+        assert!(!per_tag.allows(&running, &tags(&["test", "build"])));
+        assert!(per_tag.allows(&running, &tags(&["test"])));
+        assert!(per_tag.allows(&running, &tags(&["lint"])));
+    }
+
+    #[test]
+    fn takes_first_allowed_entry() {
+        let queue = RunQueue::default();
+        queue.entries.lock().unwrap().extend([
+            queued("build-1", &["build"]),
+            queued("test-1", &["test"]),
+            queued("build-2", &["build"]),
+        ]);
+        let limits = limits(Some(2), &[("build", 1)]);
+
+        // L'étiquette `build` est saturée : le lancement suivant, non bloqué, passe devant.
+        let running = [tags(&["build"])];
+        assert_eq!(queue.take_next(&limits, &running).map(|run| run.queue_id).as_deref(), Some("test-1"));
+
+        // Limite globale atteinte : rien ne démarre.
+        let running = [tags(&["build"]), tags(&["test"])];
+        assert!(queue.take_next(&limits, &running).is_none());
+
+        // Une place libre : l'ordre de la file est respecté.
+        assert_eq!(queue.take_next(&limits, &[]).map(|run| run.queue_id).as_deref(), Some("build-1"));
+        assert_eq!(queue.position("build-2"), Some(0));
+    }
+
+    #[test]
+    fn cancel_drops_waiter() {
+        let queue = RunQueue::default();
+        queue.entries.lock().unwrap().push(queued("dev", &[]));
+        let (started_tx, mut started_rx) = oneshot::channel();
+        queue.waiters.lock().unwrap().insert("dev".to_string(), started_tx);
+
+        assert_eq!(queue.cancel("dev").map(|run| run.queue_id).as_deref(), Some("dev"));
+        assert!(matches!(started_rx.try_recv(), Err(oneshot::error::TryRecvError::Closed)));
+        assert!(queue.entries().is_empty());
+    }
+}
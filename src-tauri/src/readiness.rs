@@ -0,0 +1,257 @@
+// src-tauri/src/readiness.rs
+
+use crate::runner::RunEvent;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::Instant;
+
+/// Intervalle entre deux vérifications TCP ou HTTP.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Délai maximal d'une tentative de connexion.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+/// Délai d'attente par défaut de la disponibilité d'un script.
+pub const DEFAULT_READINESS_TIMEOUT: Duration = Duration::from_secs(120);
+
+fn default_host() -> String {
+    "localhost".to_string()
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+/// Condition indiquant qu'un script est prêt à être utilisé par les scripts qui en dépendent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReadinessCheck {
+    /// Une ligne de sortie correspond à l'expression régulière.
+    Output { pattern: String },
+    /// Le port TCP accepte les connexions.
+    Tcp {
+        port: u16,
+        #[serde(default = "default_host")]
+        host: String,
+    },
+    /// L'URL répond avec le statut HTTP attendu.
+    Http {
+        url: String,
+        #[serde(default = "default_status")]
+        status: u16,
+    },
+    /// Un délai fixe s'est écoulé depuis le lancement.
+    Delay { ms: u64 },
+    /// Le script s'est terminé avec succès (génération de code, migrations, ...).
+    Exit,
+}
+
+/// Résultat d'une attente de disponibilité.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Readiness {
+    /// Le script tourne et remplit la condition.
+    Ready,
+    /// Le script s'est terminé avec succès avant ou en remplissant la condition.
+    Completed,
+}
+
+/// Attend qu'une exécution remplisse la condition de disponibilité.
+/// Échoue si le script se termine en erreur ou si le délai est dépassé.
+pub async fn wait_ready(
+    check: &ReadinessCheck,
+    run_id: &str,
+    events: &mut broadcast::Receiver<RunEvent>,
+    timeout: Duration,
+) -> Result<Readiness, String> {
+    let pattern = match check {
+        ReadinessCheck::Output { pattern } => {
+            Some(Regex::new(pattern).map_err(|e| format!("Expression régulière invalide : {}", e))?)
+        }
+        _ => None,
+    };
+    let started_at = Instant::now();
+    let deadline = started_at + timeout;
+    let mut poll = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep_until(deadline) => {
+                return Err(format!("Script non prêt après {} s.", timeout.as_secs()));
+            }
+            event = events.recv() => match event {
+                Ok(RunEvent::Output { run_id: id, line }) if id == run_id => {
+                    if pattern.as_ref().is_some_and(|pattern| pattern.is_match(&line)) {
+                        return Ok(Readiness::Ready);
+                    }
+                }
+                Ok(RunEvent::Exited { run_id: id, success, code, .. }) if id == run_id => {
+                    return if success {
+                        Ok(Readiness::Completed)
+                    } else {
+                        Err(format!("Le script s'est terminé avec le code {}.", code.unwrap_or(-1)))
+                    };
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return Err("Flux d'événements fermé.".to_string()),
+            },
+            _ = poll.tick() => {
+                let ready = match check {
+                    ReadinessCheck::Tcp { port, host } => tcp_open(host, *port).await,
+                    ReadinessCheck::Http { url, status } => http_status(url).await.ok() == Some(*status),
+                    ReadinessCheck::Delay { ms } => started_at.elapsed() >= Duration::from_millis(*ms),
+                    ReadinessCheck::Output { .. } | ReadinessCheck::Exit => false,
+                };
+                if ready {
+                    return Ok(Readiness::Ready);
+                }
+            }
+        }
+    }
+}
+
+/// Indique si un port TCP accepte les connexions.
+pub async fn tcp_open(host: &str, port: u16) -> bool {
+    matches!(
+        tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect((host, port))).await,
+        Ok(Ok(_))
+    )
+}
+
+/// Envoie une requête `GET` HTTP/1.1 et retourne le code de statut de la réponse.
+/// Seul `http://` est pris en charge : les serveurs de développement sont locaux.
+pub async fn http_status(url: &str) -> Result<u16, String> {
+    let target = parse_http_url(url)?;
+
+    let request = async {
+        let mut stream = TcpStream::connect((target.host, target.port)).await?;
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nUser-Agent: dev-launcher\r\n\r\n",
+            target.path, target.authority
+        );
+        stream.write_all(request.as_bytes()).await?;
+
+        // La ligne de statut peut arriver en plusieurs segments : lecture jusqu'à sa fin.
+        let mut response = Vec::new();
+        let mut buffer = [0u8; 512];
+        while !response.windows(2).any(|window| window == b"\r\n") && response.len() < MAX_STATUS_LINE {
+            let read = stream.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            response.extend_from_slice(&buffer[..read]);
+        }
+        Ok::<_, std::io::Error>(String::from_utf8_lossy(&response).to_string())
+    };
+    let response = tokio::time::timeout(CONNECT_TIMEOUT, request)
+        .await
+        .map_err(|_| format!("Délai dépassé pour {}", url))?
+        .map_err(|e| e.to_string())?;
+
+    parse_status_line(&response).ok_or_else(|| format!("Réponse HTTP invalide de {}", url))
+}
+
+/// Taille maximale lue pour trouver la ligne de statut d'une réponse HTTP.
+const MAX_STATUS_LINE: usize = 8 * 1024;
+
+/// Destination d'une requête HTTP : hôte (sans crochets pour IPv6), port, chemin et
+/// autorité telle qu'écrite dans l'URL (pour l'en-tête `Host`).
+#[derive(Debug, PartialEq, Eq)]
+struct HttpTarget<'a> {
+    host: &'a str,
+    port: u16,
+    path: &'a str,
+    authority: &'a str,
+}
+
+fn parse_http_url(url: &str) -> Result<HttpTarget<'_>, String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("URL non prise en charge : {}", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    // `[::1]:3000`, `[::1]`, `localhost:3000` ou `localhost`.
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(bracketed) => {
+            let (host, after) = bracketed
+                .split_once(']')
+                .ok_or_else(|| format!("Adresse IPv6 invalide : {}", url))?;
+            match after {
+                "" => (host, None),
+                _ => (host, Some(after.strip_prefix(':').ok_or_else(|| format!("Port invalide : {}", url))?)),
+            }
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+    let port = match port {
+        Some(port) => port.parse().map_err(|_| format!("Port invalide : {}", url))?,
+        None => 80,
+    };
+    Ok(HttpTarget { host, port, path, authority })
+}
+
+/// Code de statut d'une réponse HTTP (`HTTP/1.1 200 OK`).
+fn parse_status_line(response: &str) -> Option<u16> {
+    let status_line = response.lines().next()?;
+    if !status_line.starts_with("HTTP/") {
+        return None;
+    }
+    status_line.split_whitespace().nth(1)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_http_urls() {
+        let target = parse_http_url("http://localhost:3000/health").unwrap();
+        assert_eq!(
+            target,
+            HttpTarget { host: "localhost", port: 3000, path: "/health", authority: "localhost:3000" }
+        );
+        assert_eq!(parse_http_url("http://localhost").unwrap().port, 80);
+        assert_eq!(parse_http_url("http://localhost").unwrap().path, "/");
+
+        let target = parse_http_url("http://[::1]:5173/").unwrap();
+        assert_eq!((target.host, target.port, target.authority), ("::1", 5173, "[::1]:5173"));
+        let target = parse_http_url("http://[::1]/status").unwrap();
+        assert_eq!((target.host, target.port, target.path), ("::1", 80, "/status"));
+
+        assert!(parse_http_url("https://localhost").is_err());
+        assert!(parse_http_url("http://localhost:abc/").is_err());
+        assert!(parse_http_url("http://[::1/").is_err());
+    }
+
+    #[test]
+    fn parses_status_lines() {
+        assert_eq!(parse_status_line("HTTP/1.1 204 No Content\r\nServer: x\r\n"), Some(204));
+        assert_eq!(parse_status_line("HTTP/1.0 500"), Some(500));
+        assert_eq!(parse_status_line("SSH-2.0-OpenSSH\r\n"), None);
+        assert_eq!(parse_status_line(""), None);
+    }
+
+    /// Le serveur envoie la ligne de statut en plusieurs segments, au-delà des premiers octets.
+    #[tokio::test]
+    async fn reads_split_status_line() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await.unwrap();
+            socket.write_all(b"HTTP/1.1 ").await.unwrap();
+            socket.flush().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            socket.write_all(b"418 I'm a teapot\r\n\r\n").await.unwrap();
+        });
+
+        assert_eq!(http_status(&format!("http://127.0.0.1:{}/", port)).await, Ok(418));
+    }
+}
@@ -0,0 +1,776 @@
+// src-tauri/src/runner.rs
+
+use crate::history::{elapsed_ms, RunRecord};
+use crate::limits::{LimitExceeded, MemoryCgroup, ResourceLimits};
+use crate::metrics;
+use crate::node_version::resolve_node;
+use crate::pidfile;
+use crate::probes::{self, ScriptHealth};
+use crate::types::{AppState, RunningProcess, ScriptSettings, Task};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime, WebviewWindow};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command as TokioCommand};
+use tokio::sync::broadcast;
+
+#[cfg(unix)]
+use crate::limits::ChildLimits;
+#[cfg(target_os = "linux")]
+use std::os::fd::AsRawFd;
+
+/// Origine d'une exécution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunTrigger {
+    #[default]
+    Manual,
+    Profile,
+    Graph,
+    Schedule,
+    Watch,
+}
+
+/// Demande d'exécution d'une commande dans le dossier d'un projet.
+#[derive(Debug, Clone)]
+pub struct LaunchRequest {
+    pub project_id: String,
+    pub script: String,
+    /// Source de la tâche, pour retrouver ses réglages ; `None` pour une commande libre.
+    pub source: Option<String>,
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+    pub env: HashMap<String, String>,
+    pub trigger: RunTrigger,
+}
+
+impl LaunchRequest {
+    /// Construit la demande de lancement d'une tâche détectée dans un projet.
+    pub fn for_task(project_id: &str, task: Task, cwd: PathBuf) -> Self {
+        LaunchRequest {
+            project_id: project_id.to_string(),
+            script: task.name,
+            source: Some(task.source),
+            program: task.program,
+            args: task.args,
+            cwd,
+            env: HashMap::new(),
+            trigger: RunTrigger::Manual,
+        }
+    }
+}
+
+/// Événement d'exécution diffusé aux tâches de fond (ordonnanceur, ...).
+#[derive(Debug, Clone)]
+pub enum RunEvent {
+    Output { run_id: String, line: String },
+    Exited {
+        run_id: String,
+        success: bool,
+        code: Option<i32>,
+        limit: Option<LimitExceeded>,
+    },
+}
+
+/// Délai laissé à un processus pour s'arrêter proprement avant d'être tué.
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Durée d'exécution au-delà de laquelle un script est considéré stable :
+/// le compteur de tentatives de redémarrage consécutives est alors remis à zéro.
+const RESTART_RESET_AFTER: Duration = Duration::from_secs(60);
+
+/// Intervalle entre deux mesures de la mémoire d'un script limité sans cgroup v2.
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Politique de redémarrage d'un script après sa fin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartMode {
+    #[default]
+    Never,
+    OnFailure,
+    Always,
+}
+
+fn default_max_retries() -> u32 {
+    5
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    1_000
+}
+
+fn default_max_backoff_ms() -> u64 {
+    30_000
+}
+
+/// Redémarrage automatique d'un script, avec un délai exponentiel entre les tentatives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestartPolicy {
+    #[serde(default)]
+    pub mode: RestartMode,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy {
+            mode: RestartMode::Never,
+            max_retries: default_max_retries(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Indique si une fin d'exécution doit déclencher un redémarrage.
+    pub fn should_restart(&self, success: bool) -> bool {
+        match self.mode {
+            RestartMode::Never => false,
+            RestartMode::OnFailure => !success,
+            RestartMode::Always => true,
+        }
+    }
+
+    /// Délai avant la tentative `attempt` (à partir de 1) : doublé à chaque tentative, plafonné.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        Duration::from_millis(self.initial_backoff_ms.saturating_mul(factor).min(self.max_backoff_ms))
+    }
+}
+
+/// Commande résolue, relancée à l'identique en cas de redémarrage.
+struct SpawnSpec {
+    program: PathBuf,
+    args: Vec<String>,
+    cwd: PathBuf,
+    env: HashMap<String, String>,
+    path_env: Option<OsString>,
+    limits: ResourceLimits,
+    /// Groupe de contrôle limitant la mémoire, partagé par les redémarrages successifs.
+    cgroup: Option<MemoryCgroup>,
+}
+
+impl SpawnSpec {
+    fn spawn(&self) -> std::io::Result<Child> {
+        let mut process = TokioCommand::new(&self.program);
+        if let Some(path_env) = self.path_env.as_ref() {
+            process.env("PATH", path_env);
+        }
+
+        // Chaque script dispose de son propre groupe de processus pour pouvoir arrêter ses enfants.
+        #[cfg(unix)]
+        process.process_group(0);
+
+        // Limites appliquées dans l'enfant avant l'exécution du programme. La mémoire est limitée
+        // par le groupe de contrôle s'il a pu être créé, mesurée pendant l'exécution sinon (`supervise`).
+        #[cfg(target_os = "linux")]
+        let cgroup_procs = self.cgroup.as_ref().map(MemoryCgroup::open_procs).transpose()?;
+        #[cfg(unix)]
+        {
+            let limits = ChildLimits {
+                nice: self.limits.nice,
+                max_open_files: self.limits.max_open_files,
+                #[cfg(target_os = "linux")]
+                cgroup_procs: cgroup_procs.as_ref().map(AsRawFd::as_raw_fd),
+                #[cfg(not(target_os = "linux"))]
+                cgroup_procs: None,
+            };
+            if !limits.is_empty() {
+                // SAFETY: `apply` n'effectue que des appels système, sans allocation ni verrou.
+                unsafe { process.pre_exec(move || limits.apply()) };
+            }
+        }
+
+        process
+            .args(&self.args)
+            .envs(&self.env)
+            .current_dir(&self.cwd)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+    }
+}
+
+/// Lance une commande de projet et relaie sa sortie vers la fenêtre principale
+/// (`script_output`, `script_error`, `script_finished`, `script_restarting`).
+/// Retourne l'identifiant d'exécution une fois le processus démarré.
+pub async fn launch<R: Runtime>(app: AppHandle<R>, request: LaunchRequest) -> Result<String, String> {
+    // 1. Récupération de la fenêtre principale via get_webview_window
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "La fenêtre principale n'a pas été trouvée".to_string())?;
+
+    let run_id = uuid::Uuid::new_v4().to_string();
+    let state = app.state::<AppState>();
+    let LaunchRequest {
+        project_id: id,
+        script,
+        source,
+        program: program_name,
+        args,
+        cwd,
+        env,
+        trigger,
+    } = request;
+
+    info!("Exécution du script '{}' pour le projet ID '{}'", script, id);
+
+    // 2. Résolution de l'exécutable
+    let toolchain = Arc::clone(&state.toolchain);
+    let probe_program = program_name.clone();
+    let program = match tauri::async_runtime::spawn_blocking(move || toolchain.probe(&probe_program)).await {
+        Ok(Some(tool)) => PathBuf::from(tool.path),
+        _ => {
+            error!("Exécutable '{}' introuvable", program_name);
+            let message = toolchain_report_message(&state, &program_name);
+            let _ = window.emit("script_error", json!({ "id": id, "output": message }));
+            return Err(message);
+        }
+    };
+
+    // 3. Résolution de la version de Node exigée par le projet
+    let toolchain = Arc::clone(&state.toolchain);
+    let node_path = cwd.clone();
+    let node_resolution = tauri::async_runtime::spawn_blocking(move || {
+        let default_node = toolchain.probe("node");
+        resolve_node(&node_path, default_node.and_then(|node| node.version).as_deref())
+    })
+    .await
+    .ok();
+
+    let mut path_env = None;
+    if let Some(resolution) = node_resolution {
+        if let Some(node) = resolution.resolved.as_ref() {
+            info!("Utilisation de Node {} ({}) pour le projet ID '{}'", node.version, node.manager, id);
+            let current_path = std::env::var_os("PATH").unwrap_or_default();
+            let paths = std::iter::once(PathBuf::from(&node.bin_dir)).chain(std::env::split_paths(&current_path));
+            path_env = std::env::join_paths(paths).ok();
+        } else if resolution.mismatch {
+            warn!("Aucune version de Node installée ne correspond au projet ID '{}'", id);
+            let _ = window.emit("node_version_mismatch", json!({
+                "id": id,
+                "requirement": resolution.requirement,
+            }));
+        }
+    }
+
+    // 4. Tentative de lancement de la commande, dans les limites de ressources du script
+    let settings = script_settings(&state, &id, source.as_deref(), &script).unwrap_or_default();
+    let cgroup = settings
+        .limits
+        .max_memory_bytes()
+        .and_then(|bytes| MemoryCgroup::create(&run_id, bytes));
+    if settings.limits.max_memory_mb.is_some() && cgroup.is_none() {
+        info!(
+            "cgroup v2 indisponible : la mémoire du script '{}' est mesurée périodiquement pour le projet ID '{}'",
+            script, id
+        );
+    }
+    // SAFETY: geteuid ne fait que lire l'identifiant effectif du processus.
+    #[cfg(unix)]
+    if settings.limits.nice.is_some_and(|nice| nice < 0) && unsafe { libc::geteuid() } != 0 {
+        warn!("Priorité négative ignorée sans privilèges pour le script '{}'", script);
+    }
+    #[cfg(not(unix))]
+    if settings.limits.max_memory_mb.is_some() || settings.limits.nice.is_some() || settings.limits.max_open_files.is_some() {
+        warn!("Seule la durée maximale d'exécution est prise en charge sur cette plateforme.");
+    }
+    let mut spec = SpawnSpec {
+        program,
+        args,
+        cwd,
+        env,
+        path_env,
+        limits: settings.limits,
+        cgroup,
+    };
+    let spawned = match spec.spawn() {
+        // Le groupe a pu être créé sans que le processus puisse y être déplacé (EACCES quand
+        // `cgroup.procs` n'est pas délégué) : nouvelle tentative sans cgroup, avec mesure périodique.
+        Err(e) if spec.cgroup.is_some() => {
+            warn!(
+                "Impossible de placer le script '{}' dans son cgroup ({}) : sa mémoire est mesurée périodiquement",
+                script, e
+            );
+            spec.cgroup = None;
+            spec.spawn()
+        }
+        spawned => spawned,
+    };
+    let child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            error!("Erreur lors du lancement du script: {}", e);
+            let message = format!("Erreur lors de l'exécution du script: {}", e);
+            let _ = window.emit("script_error", json!({ "id": id, "output": message }));
+            return Err(message);
+        }
+    };
+
+    let process = RunningProcess {
+        run_id: run_id.clone(),
+        project_id: id.clone(),
+        script: script.clone(),
+        source: source.clone(),
+        manager: program_name,
+        pid: child.id(),
+        started_at: chrono::Local::now().to_rfc3339(),
+        trigger,
+        restarts: 0,
+        stop_requested: false,
+        restart_requested: false,
+        health: None,
+        url: None,
+        adopted: false,
+    };
+    pidfile::write(&process);
+    state.store.register_process(process);
+
+    // 5. Suivi du processus jusqu'à sa fin définitive, et de son état de santé s'il a une sonde
+    if let Some(check) = settings.health_check {
+        tokio::spawn(probes::monitor(app.clone(), run_id.clone(), check, state.run_events.subscribe()));
+    }
+    tokio::spawn(supervise(app.clone(), window, run_id.clone(), id, script, spec, child));
+
+    Ok(run_id)
+}
+
+/// Relaie la sortie du processus, attend sa fin et le redémarre selon sa politique.
+async fn supervise<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    run_id: String,
+    id: String,
+    script: String,
+    spec: SpawnSpec,
+    mut child: Child,
+) {
+    let state = app.state::<AppState>();
+    let source = state.store.process(&run_id).and_then(|process| process.source);
+    let mut consecutive_restarts = 0;
+    let mut oom_kills = spec.cgroup.as_ref().map_or(0, MemoryCgroup::oom_kills);
+
+    let (success, code, limit) = loop {
+        forward_output(&window, &state.run_events, &run_id, &id, &mut child);
+        let started = Instant::now();
+        let attempt_started_at = chrono::Local::now().to_rfc3339();
+        // La durée maximale s'applique à chaque lancement, redémarrages compris.
+        let deadline = spec.limits.timeout().map(|timeout| tokio::time::Instant::now() + timeout);
+        let memory_limit = spec
+            .limits
+            .max_memory_bytes()
+            .filter(|_| spec.cgroup.is_none())
+            .zip(child.id());
+
+        // Attente de la fin du processus, dans la limite de sa durée d'exécution et de sa mémoire maximales
+        let (wait_result, mut limit) = tokio::select! {
+            result = child.wait() => (result, None),
+            _ = wait_deadline(deadline) => {
+                warn!("Durée maximale dépassée pour le script '{}' du projet ID '{}'", script, id);
+                (stop_child(&mut child).await, Some(LimitExceeded::Timeout))
+            }
+            _ = wait_memory_exceeded(memory_limit) => {
+                warn!("Mémoire maximale dépassée pour le script '{}' du projet ID '{}'", script, id);
+                (stop_child(&mut child).await, Some(LimitExceeded::Memory))
+            }
+        };
+        let signal = wait_result.as_ref().ok().and_then(exit_signal);
+        if let Some(cgroup) = spec.cgroup.as_ref() {
+            let kills = cgroup.oom_kills();
+            if kills > oom_kills {
+                warn!("Mémoire maximale dépassée pour le script '{}' du projet ID '{}'", script, id);
+                limit = limit.or(Some(LimitExceeded::Memory));
+                oom_kills = kills;
+            }
+        }
+
+        // Gestion du résultat : un script arrêté pour dépassement d'une limite est en échec
+        let (success, code) = match wait_result {
+            Ok(status) if limit.is_some() => {
+                let output = match limit {
+                    Some(LimitExceeded::Memory) => format!(
+                        "Le script a été arrêté : mémoire maximale de {} Mo dépassée",
+                        spec.limits.max_memory_mb.unwrap_or_default()
+                    ),
+                    _ => format!(
+                        "Le script a été arrêté : durée maximale de {} s dépassée",
+                        spec.limits.timeout_secs.unwrap_or_default()
+                    ),
+                };
+                let _ = window.emit("script_error", json!({ "id": id, "output": output, "limit": limit }));
+                (false, status.code())
+            }
+            Ok(status) => {
+                if status.success() {
+                    info!("Script '{}' terminé avec succès pour le projet ID '{}'", script, id);
+                    let _ = window.emit("script_finished", json!({"id": id}));
+                } else {
+                    warn!(
+                        "Script '{}' terminé avec un code d'erreur {} pour le projet ID '{}'",
+                        script,
+                        status.code().unwrap_or(-1),
+                        id
+                    );
+                    let payload = json!({
+                        "id": id,
+                        "output": format!("Le script a terminé avec un code d'erreur {}", status.code().unwrap_or(-1)),
+                    });
+                    let _ = window.emit("script_error", payload);
+                }
+                (status.success(), status.code())
+            }
+            Err(e) => {
+                error!("Erreur lors de l'attente du script: {}", e);
+                let payload = json!({
+                    "id": id,
+                    "output": format!("Erreur lors de l'attente du script: {}", e),
+                });
+                let _ = window.emit("script_error", payload);
+                (false, None)
+            }
+        };
+
+        // Enregistrement de la tentative dans l'historique, avant un éventuel redémarrage
+        if let Some(process) = state.store.process(&run_id) {
+            let ended_at = chrono::Local::now();
+            state.history.append(&RunRecord {
+                run_id: run_id.clone(),
+                project_id: id.clone(),
+                script: script.clone(),
+                program: process.manager,
+                args: spec.args.clone(),
+                trigger: process.trigger,
+                duration_ms: elapsed_ms(&attempt_started_at, &ended_at),
+                started_at: attempt_started_at,
+                ended_at: ended_at.to_rfc3339(),
+                success,
+                exit_code: code,
+                signal,
+                restarts: process.restarts,
+                limit,
+            });
+        }
+
+        // Redémarrage éventuel, sauf si l'arrêt a été demandé
+        let policy = script_settings(&state, &id, source.as_deref(), &script)
+            .map(|settings| settings.restart)
+            .unwrap_or_default();
+        let forced = state
+            .store
+            .update_process(&run_id, |process| std::mem::take(&mut process.restart_requested))
+            .unwrap_or(false);
+        if limit == Some(LimitExceeded::Timeout)
+            || is_stop_requested(&state, &run_id)
+            || !(forced || policy.should_restart(success))
+        {
+            break (success, code, limit);
+        }
+        // Un redémarrage demandé n'est pas une tentative : il remet le compteur à zéro.
+        if forced || started.elapsed() >= RESTART_RESET_AFTER {
+            consecutive_restarts = 0;
+        }
+        if !forced && consecutive_restarts >= policy.max_retries {
+            warn!("Script '{}' abandonné après {} redémarrages pour le projet ID '{}'", script, consecutive_restarts, id);
+            let payload = json!({
+                "id": id,
+                "output": format!("Abandon après {} tentatives de redémarrage.", consecutive_restarts),
+            });
+            let _ = window.emit("script_error", payload);
+            break (success, code, limit);
+        }
+
+        let delay = if forced {
+            Duration::ZERO
+        } else {
+            consecutive_restarts += 1;
+            policy.backoff(consecutive_restarts)
+        };
+        state.store.update_process(&run_id, |process| {
+            process.restarts += 1;
+            process.pid = None;
+            process.health = process.health.map(|_| ScriptHealth::Starting);
+        });
+        info!(
+            "Redémarrage du script '{}' (tentative {}) dans {} ms pour le projet ID '{}'",
+            script,
+            consecutive_restarts,
+            delay.as_millis(),
+            id
+        );
+        let _ = window.emit("script_restarting", json!({
+            "id": id,
+            "run_id": run_id,
+            "attempt": consecutive_restarts,
+            "max_retries": policy.max_retries,
+            "delay_ms": delay.as_millis() as u64,
+            "code": code,
+        }));
+
+        tokio::time::sleep(delay).await;
+        if is_stop_requested(&state, &run_id) {
+            break (success, code, limit);
+        }
+        match spec.spawn() {
+            Ok(restarted) => {
+                if let Some(process) = state.store.update_process(&run_id, |process| {
+                    process.pid = restarted.id();
+                    process.clone()
+                }) {
+                    pidfile::write(&process);
+                }
+                child = restarted;
+            }
+            Err(e) => {
+                error!("Erreur lors du redémarrage du script: {}", e);
+                let payload = json!({
+                    "id": id,
+                    "output": format!("Erreur lors du redémarrage du script: {}", e),
+                });
+                let _ = window.emit("script_error", payload);
+                break (false, None, None);
+            }
+        }
+    };
+
+    pidfile::remove(&run_id);
+    state.store.unregister_process(&run_id);
+    let _ = state.run_events.send(RunEvent::Exited {
+        run_id,
+        success,
+        code,
+        limit,
+    });
+}
+
+#[cfg(unix)]
+fn exit_signal(status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: &ExitStatus) -> Option<i32> {
+    None
+}
+
+/// Attend l'échéance donnée, indéfiniment s'il n'y en a pas.
+async fn wait_deadline(deadline: Option<tokio::time::Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Attend que la mémoire résidente d'un processus et de ses enfants dépasse la limite donnée,
+/// indéfiniment sans limite.
+async fn wait_memory_exceeded(limit: Option<(u64, u32)>) {
+    let Some((max_bytes, pid)) = limit else {
+        return std::future::pending().await;
+    };
+    let mut ticks = tokio::time::interval(MEMORY_SAMPLE_INTERVAL);
+    loop {
+        ticks.tick().await;
+        let memory = tauri::async_runtime::spawn_blocking(move || metrics::tree_memory_bytes(pid))
+            .await
+            .ok()
+            .flatten();
+        if memory.is_some_and(|memory| memory > max_bytes) {
+            return;
+        }
+    }
+}
+
+/// Arrête un processus ayant dépassé une limite, de force après le délai de grâce.
+async fn stop_child(child: &mut Child) -> std::io::Result<ExitStatus> {
+    if let Some(pid) = child.id() {
+        let _ = terminate_process_tree(pid, false);
+        if let Ok(result) = tokio::time::timeout(STOP_GRACE_PERIOD, child.wait()).await {
+            return result;
+        }
+        let _ = terminate_process_tree(pid, true);
+    }
+    child.wait().await
+}
+
+/// Relaie les sorties standard (`script_output`) et d'erreur (`script_error`) d'un processus.
+fn forward_output<R: Runtime>(
+    window: &WebviewWindow<R>,
+    events: &broadcast::Sender<RunEvent>,
+    run_id: &str,
+    id: &str,
+    child: &mut Child,
+) {
+    // Gestion de la sortie standard (stdout)
+    if let Some(stdout) = child.stdout.take() {
+        let window_clone = window.clone();
+        let id_clone = id.to_string();
+        let events = events.clone();
+        let output_run_id = run_id.to_string();
+        tokio::spawn(async move {
+            let reader = BufReader::new(stdout);
+            let mut lines = reader.lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let _ = events.send(RunEvent::Output {
+                    run_id: output_run_id.clone(),
+                    line: line.clone(),
+                });
+                let payload = json!({
+                    "id": id_clone,
+                    "output": line,
+                });
+                info!("Emitting script_output: {:?}", payload);
+                if let Err(e) = window_clone.emit("script_output", payload) {
+                    error!("Erreur lors de l'émission de script_output: {}", e);
+                }
+            }
+        });
+    }
+
+    // Gestion des erreurs standard (stderr)
+    if let Some(stderr) = child.stderr.take() {
+        let window_clone = window.clone();
+        let id_clone = id.to_string();
+        let events = events.clone();
+        let output_run_id = run_id.to_string();
+        tokio::spawn(async move {
+            let reader = BufReader::new(stderr);
+            let mut lines = reader.lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let _ = events.send(RunEvent::Output {
+                    run_id: output_run_id.clone(),
+                    line: line.clone(),
+                });
+                let payload = json!({
+                    "id": id_clone,
+                    "output": line,
+                });
+                warn!("Emitting script_error: {:?}", payload);
+                if let Err(e) = window_clone.emit("script_error", payload) {
+                    error!("Erreur lors de l'émission de script_error: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Réglages configurés pour un script, relus à chaque utilisation pour prendre en compte les modifications.
+fn script_settings(state: &AppState, project_id: &str, source: Option<&str>, script: &str) -> Option<ScriptSettings> {
+    state
+        .store
+        .project(project_id)
+        .and_then(|project| project.settings_for(source, script).cloned())
+}
+
+fn is_stop_requested(state: &AppState, run_id: &str) -> bool {
+    state
+        .store
+        .process(run_id)
+        .map(|process| process.stop_requested)
+        .unwrap_or(true)
+}
+
+/// Message d'erreur expliquant l'absence d'un exécutable.
+pub fn toolchain_report_message(state: &AppState, program: &str) -> String {
+    state
+        .toolchain
+        .report(program)
+        .suggestion
+        .unwrap_or_else(|| format!("{} est introuvable.", program))
+}
+
+/// Arrête un script en cours : SIGTERM sur son groupe de processus, puis SIGKILL
+/// s'il tourne toujours après un délai de grâce (arborescence tuée via `taskkill` sous Windows).
+/// Un script en attente de redémarrage n'est simplement pas relancé.
+pub fn stop<R: Runtime>(app: &AppHandle<R>, run_id: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let process = state
+        .store
+        .update_process(run_id, |process| {
+            process.stop_requested = true;
+            process.clone()
+        })
+        .ok_or_else(|| format!("Exécution {} introuvable.", run_id))?;
+    let Some(pid) = process.pid else {
+        info!("Redémarrage du script '{}' annulé pour le projet ID '{}'", process.script, process.project_id);
+        return Ok(());
+    };
+
+    info!("Arrêt du script '{}' (PID {}) pour le projet ID '{}'", process.script, pid, process.project_id);
+    terminate_process_tree(pid, false)?;
+
+    let app = app.clone();
+    let run_id = run_id.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep(STOP_GRACE_PERIOD).await;
+        let still_running = app
+            .state::<AppState>()
+            .store
+            .process(&run_id)
+            .is_some_and(|process| process.pid == Some(pid));
+        if still_running {
+            warn!("Le script (PID {}) ne s'est pas arrêté, arrêt forcé.", pid);
+            let _ = terminate_process_tree(pid, true);
+        }
+    });
+    Ok(())
+}
+
+/// Redémarre un script en cours : le processus est arrêté puis relancé immédiatement,
+/// quelle que soit sa politique de redémarrage.
+pub fn request_restart<R: Runtime>(app: &AppHandle<R>, run_id: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if state.store.process(run_id).is_some_and(|process| process.adopted) {
+        return Err("Un script adopté ne peut pas être redémarré : arrêtez-le puis relancez-le.".to_string());
+    }
+    let process = state
+        .store
+        .update_process(run_id, |process| {
+            process.restart_requested = true;
+            process.clone()
+        })
+        .ok_or_else(|| format!("Exécution {} introuvable.", run_id))?;
+    let pid = process
+        .pid
+        .ok_or_else(|| format!("Le script {} est déjà en cours de redémarrage.", process.script))?;
+
+    info!("Redémarrage du script '{}' (PID {}) pour le projet ID '{}'", process.script, pid, process.project_id);
+    terminate_process_tree(pid, false)
+}
+
+#[cfg(unix)]
+fn terminate_process_tree(pid: u32, force: bool) -> Result<(), String> {
+    let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+    // SAFETY: killpg n'accède à aucune mémoire ; le groupe a été créé au lancement avec `process_group(0)`.
+    if unsafe { libc::killpg(pid as libc::pid_t, signal) } != 0 {
+        return Err(format!("Impossible d'arrêter le processus {} : {}", pid, std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn terminate_process_tree(pid: u32, _force: bool) -> Result<(), String> {
+    let output = std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/T", "/F"])
+        .output()
+        .map_err(|e| format!("Impossible d'arrêter le processus {} : {}", pid, e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
@@ -0,0 +1,292 @@
+// src-tauri/src/scheduler.rs
+
+use crate::queue::submit;
+use crate::runner::{LaunchRequest, RunTrigger};
+use crate::script::find_task;
+use crate::types::{AppState, ProjectConfig};
+use chrono::{DateTime, Local};
+use croner::Cron;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Intervalle maximal entre deux réveils de l'ordonnanceur, pour prendre en compte
+/// les modifications de planification et les sorties de veille.
+const MAX_SLEEP: Duration = Duration::from_secs(30);
+/// Retard au-delà duquel une échéance est considérée comme manquée (application fermée, veille, ...).
+const MISSED_AFTER: Duration = Duration::from_secs(60);
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Traitement des échéances manquées.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedRunPolicy {
+    /// Les échéances manquées sont ignorées.
+    #[default]
+    Skip,
+    /// Le script est lancé une fois dès que possible, quel que soit le nombre d'échéances manquées.
+    RunOnce,
+}
+
+/// Lancement planifié d'un script selon une expression cron (`30 8 * * 1-5`, `*/30 * * * *`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: String,
+    pub script: String,
+    /// Source de la tâche (`package.json`, `Makefile`, ...) ; la première tâche du même nom sinon.
+    #[serde(default)]
+    pub source: Option<String>,
+    pub cron: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub missed: MissedRunPolicy,
+    #[serde(default)]
+    pub last_run_at: Option<String>,
+}
+
+/// Planification d'un projet accompagnée de sa prochaine échéance.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleInfo {
+    pub project_id: String,
+    #[serde(flatten)]
+    pub schedule: Schedule,
+    pub next_run_at: Option<String>,
+}
+
+/// Analyse une expression cron à cinq champs (ou six, avec les secondes).
+pub fn parse_cron(expression: &str) -> Result<Cron, String> {
+    Cron::new(expression)
+        .with_seconds_optional()
+        .parse()
+        .map_err(|e| format!("Expression cron invalide « {} » : {}", expression, e))
+}
+
+/// Prochaine échéance d'une planification après l'instant donné.
+pub fn next_run(schedule: &Schedule, after: &DateTime<Local>) -> Option<DateTime<Local>> {
+    parse_cron(&schedule.cron).ok()?.find_next_occurrence(after, false).ok()
+}
+
+/// Décision de l'ordonnanceur pour une planification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Due {
+    /// Prochaine échéance, pas encore atteinte.
+    Waiting(DateTime<Local>),
+    /// Échéance atteinte, ou manquée avec la politique `RunOnce` : le script est lancé.
+    Run,
+    /// Échéance manquée depuis plus de `MISSED_AFTER`, ignorée selon la politique `Skip`.
+    SkipMissed,
+}
+
+/// Décide du sort de la première échéance suivant `cursor` à l'instant `now` ;
+/// `None` si la planification n'a plus d'échéance.
+fn due(schedule: &Schedule, cursor: &DateTime<Local>, now: &DateTime<Local>) -> Option<Due> {
+    let next = next_run(schedule, cursor)?;
+    if next > *now {
+        return Some(Due::Waiting(next));
+    }
+    let missed = (*now - next).to_std().unwrap_or_default() > MISSED_AFTER;
+    Some(if missed && schedule.missed == MissedRunPolicy::Skip {
+        Due::SkipMissed
+    } else {
+        Due::Run
+    })
+}
+
+/// Liste les planifications, éventuellement limitées à un projet, avec leur prochaine échéance.
+pub fn list(projects: &[ProjectConfig], project_id: Option<&str>) -> Vec<ScheduleInfo> {
+    let now = Local::now();
+    projects
+        .iter()
+        .filter(|project| project_id.iter().all(|id| *id == project.id))
+        .flat_map(|project| {
+            project.schedules.iter().map(move |schedule| ScheduleInfo {
+                project_id: project.id.clone(),
+                next_run_at: schedule
+                    .enabled
+                    .then(|| next_run(schedule, &now))
+                    .flatten()
+                    .map(|next| next.to_rfc3339()),
+                schedule: schedule.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Démarre l'ordonnanceur des lancements planifiés.
+pub fn start<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        // Instant à partir duquel chercher la prochaine échéance de chaque planification.
+        let mut cursors: HashMap<String, DateTime<Local>> = HashMap::new();
+
+        loop {
+            let now = Local::now();
+            let mut wake_at = now + chrono::Duration::from_std(MAX_SLEEP).unwrap_or_default();
+
+            for project in app.state::<AppState>().store.projects() {
+                for schedule in &project.schedules {
+                    // Une planification réactivée ne rattrape pas les échéances de sa période de désactivation.
+                    if !schedule.enabled {
+                        cursors.remove(&schedule.id);
+                        continue;
+                    }
+                    let cursor = *cursors.entry(schedule.id.clone()).or_insert_with(|| {
+                        schedule
+                            .last_run_at
+                            .as_deref()
+                            .and_then(|last_run_at| DateTime::parse_from_rfc3339(last_run_at).ok())
+                            .map(|last_run_at| last_run_at.with_timezone(&Local))
+                            .unwrap_or(now)
+                    });
+                    match due(schedule, &cursor, &now) {
+                        None => {}
+                        Some(Due::Waiting(next)) => wake_at = wake_at.min(next),
+                        // Échéance atteinte : les échéances manquées sont regroupées en une seule.
+                        Some(Due::SkipMissed) => {
+                            cursors.insert(schedule.id.clone(), now);
+                            info!("Échéance manquée ignorée pour le script '{}' du projet {}", schedule.script, project.name);
+                        }
+                        Some(Due::Run) => {
+                            cursors.insert(schedule.id.clone(), now);
+                            run_scheduled(&app, &project, schedule).await;
+                        }
+                    }
+                }
+            }
+
+            let delay = (wake_at - Local::now()).to_std().unwrap_or_default();
+            tokio::time::sleep(delay.min(MAX_SLEEP)).await;
+        }
+    });
+}
+
+/// Lance un script planifié, sauf s'il est déjà en cours (`scheduled_run`).
+async fn run_scheduled<R: Runtime>(app: &AppHandle<R>, project: &ProjectConfig, schedule: &Schedule) {
+    let state = app.state::<AppState>();
+    let already_running = state
+        .store
+        .processes()
+        .iter()
+        .any(|process| process.runs(&project.id, schedule.source.as_deref(), &schedule.script));
+    if already_running {
+        warn!("Script planifié '{}' déjà en cours pour le projet {}", schedule.script, project.name);
+        return;
+    }
+
+    let path = PathBuf::from(&project.path);
+    let result = match find_task(&path, schedule.source.as_deref(), &schedule.script) {
+        Some(task) => {
+            let request = LaunchRequest {
+                trigger: RunTrigger::Schedule,
+                ..LaunchRequest::for_task(&project.id, task, path)
+            };
+            submit(app.clone(), request).await
+        }
+        None => Err(format!("Tâche {} introuvable pour {}.", schedule.script, project.name)),
+    };
+
+    let ran_at = Local::now().to_rfc3339();
+    if let Err(e) = state.store.update(|config| {
+        let schedule = config
+            .project_folders
+            .iter_mut()
+            .filter(|p| p.id == project.id)
+            .flat_map(|p| p.schedules.iter_mut())
+            .find(|s| s.id == schedule.id);
+        if let Some(schedule) = schedule {
+            schedule.last_run_at = Some(ran_at.clone());
+        }
+    }) {
+        error!("Erreur lors de la mise à jour de la configuration : {}", e);
+    }
+
+    if let Err(e) = &result {
+        error!("Échec du lancement planifié du script '{}' : {}", schedule.script, e);
+    }
+    let payload = json!({
+        "id": project.id,
+        "schedule_id": schedule.id,
+        "script": schedule.script,
+        "ran_at": ran_at,
+        "submission": result.as_ref().ok(),
+        "error": result.as_ref().err(),
+    });
+    if let Err(e) = app.emit("scheduled_run", payload) {
+        error!("Erreur lors de l'émission de scheduled_run: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(cron: &str, missed: MissedRunPolicy) -> Schedule {
+        Schedule {
+            id: "schedule".to_string(),
+            script: "backup".to_string(),
+            source: None,
+            cron: cron.to_string(),
+            enabled: true,
+            missed,
+            last_run_at: None,
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 5, day, hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn parses_cron_expressions() {
+        assert!(parse_cron("30 8 * * 1-5").is_ok());
+        assert!(parse_cron("*/30 * * * *").is_ok());
+        assert!(parse_cron("0 */5 * * * *").is_ok());
+        assert!(parse_cron("61 * * * *").is_err());
+        assert!(parse_cron("tous les jours").unwrap_err().starts_with("Expression cron invalide « tous les jours »"));
+    }
+
+    #[test]
+    fn finds_next_run() {
+        // Lundi 6 mai 2024.
+        let weekdays = schedule("30 8 * * 1-5", MissedRunPolicy::Skip);
+        assert_eq!(next_run(&weekdays, &at(6, 8, 0, 0)), Some(at(6, 8, 30, 0)));
+        // Une échéance égale à l'instant de départ n'est pas retenue.
+        assert_eq!(next_run(&weekdays, &at(6, 8, 30, 0)), Some(at(7, 8, 30, 0)));
+        // Vendredi soir : prochaine échéance le lundi suivant.
+        assert_eq!(next_run(&weekdays, &at(10, 18, 0, 0)), Some(at(13, 8, 30, 0)));
+
+        assert_eq!(next_run(&schedule("*/15 * * * *", MissedRunPolicy::Skip), &at(6, 9, 7, 0)), Some(at(6, 9, 15, 0)));
+        assert_eq!(next_run(&schedule("invalide", MissedRunPolicy::Skip), &at(6, 9, 0, 0)), None);
+    }
+
+    #[test]
+    fn waits_for_future_runs() {
+        let hourly = schedule("0 * * * *", MissedRunPolicy::Skip);
+        assert_eq!(due(&hourly, &at(6, 9, 10, 0), &at(6, 9, 20, 0)), Some(Due::Waiting(at(6, 10, 0, 0))));
+        assert_eq!(due(&schedule("invalide", MissedRunPolicy::Skip), &at(6, 9, 0, 0), &at(6, 9, 0, 0)), None);
+    }
+
+    #[test]
+    fn runs_when_due() {
+        let hourly = schedule("0 * * * *", MissedRunPolicy::Skip);
+        assert_eq!(due(&hourly, &at(6, 9, 59, 0), &at(6, 10, 0, 0)), Some(Due::Run));
+        // Un léger retard (réveil de l'ordonnanceur) n'est pas une échéance manquée.
+        assert_eq!(due(&hourly, &at(6, 9, 59, 0), &at(6, 10, 0, 45)), Some(Due::Run));
+    }
+
+    #[test]
+    fn applies_missed_policy() {
+        // Application fermée de 9 h 30 à 13 h : plusieurs échéances manquées.
+        let (cursor, now) = (at(6, 9, 30, 0), at(6, 13, 0, 0));
+        assert_eq!(due(&schedule("0 * * * *", MissedRunPolicy::Skip), &cursor, &now), Some(Due::SkipMissed));
+        assert_eq!(due(&schedule("0 * * * *", MissedRunPolicy::RunOnce), &cursor, &now), Some(Due::Run));
+    }
+}
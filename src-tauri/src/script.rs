@@ -1,43 +1,65 @@
-use crate::types::FetchPackageJson;
+use crate::package_json::read_package_json;
+use crate::project_type::read_toml;
+use crate::types::{DetectionReason, FetchPackageJson, Task};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::fs;
+use std::path::Path;
 
-const PACKAGE_MANAGERS_AND_LOCKFILES: &[(&str, &str)] = &[
+pub const PACKAGE_MANAGERS_AND_LOCKFILES: &[(&str, &str)] = &[
     ("pnpm", "pnpm-lock.yaml"),
     ("yarn", "yarn.lock"),
     ("bun", "bun.lockb"),
+    ("bun", "bun.lock"),
     ("npm", "package-lock.json"),
+    ("npm", "npm-shrinkwrap.json"),
 ];
 
+/// Gestionnaires reconnus dans le champ "packageManager" (Corepack).
+const KNOWN_PACKAGE_MANAGERS: &[&str] = &["npm", "pnpm", "yarn", "bun"];
+
 /// Détecte le gestionnaire de paquets et extrait les scripts définis dans package.json
-pub fn detect_package_manager_and_scripts(project_dir: &PathBuf) -> Option<FetchPackageJson> {
-    // Définir le chemin vers le fichier `package.json`
-    let package_json_file_path = project_dir.join("package.json");
+pub fn detect_package_manager_and_scripts(project_dir: &Path) -> Option<FetchPackageJson> {
+    // Ouvrir et lire le fichier `package.json`
+    let package_json_data = read_package_json(project_dir)?;
+    Some(detect_from_package_json(project_dir, &package_json_data))
+}
 
-    // Vérifie l'existence de `package.json`
-    if !package_json_file_path.exists() {
-        return None;
+/// Détecte le gestionnaire de paquets et extrait les scripts à partir d'un package.json déjà analysé
+pub fn detect_from_package_json(project_dir: &Path, package_json_data: &Value) -> FetchPackageJson {
+    // Gestionnaires dont un fichier de lock est présent, dans l'ordre de priorité
+    let lockfile_managers: Vec<(&str, &str)> = PACKAGE_MANAGERS_AND_LOCKFILES
+        .iter()
+        .filter(|(_, lockfile_name)| project_dir.join(lockfile_name).exists())
+        .copied()
+        .collect();
+    let mut warnings = Vec::new();
+
+    let mut distinct_managers: Vec<&str> = lockfile_managers.iter().map(|(manager, _)| *manager).collect();
+    distinct_managers.dedup();
+    if distinct_managers.len() > 1 {
+        let lockfiles: Vec<&str> = lockfile_managers.iter().map(|(_, lockfile)| *lockfile).collect();
+        warnings.push(format!("Fichiers de lock en conflit : {}", lockfiles.join(", ")));
     }
 
-    // Identifier le gestionnaire de paquets en fonction de la présence des fichiers de lock
-    let detected_package_manager = PACKAGE_MANAGERS_AND_LOCKFILES
-        .iter()
-        .find_map(|(manager_name, lockfile_name)| {
-            if project_dir.join(lockfile_name).exists() {
-                Some(manager_name.to_string())
-            } else {
-                None
+    // Le champ "packageManager" (ex. "pnpm@9.1.0") est prioritaire sur les fichiers de lock
+    let (detected_package_manager, version, reason) = match parse_package_manager_field(package_json_data) {
+        Some((manager, version)) => {
+            if let Some((_, lockfile)) = lockfile_managers.first() {
+                if !distinct_managers.contains(&manager.as_str()) {
+                    warnings.push(format!(
+                        "Le champ packageManager indique {} mais le fichier de lock est {}",
+                        manager, lockfile
+                    ));
+                }
             }
-        })
-        .unwrap_or_else(|| "npm".to_string());
-
-    // Ouvrir et lire le fichier `package.json`
-    let package_file = File::open(&package_json_file_path).ok()?;
-    let package_file_reader = BufReader::new(package_file);
-    let package_json_data: Value = serde_json::from_reader(package_file_reader).ok()?;
+            (manager, version, DetectionReason::PackageManagerField)
+        }
+        None => match lockfile_managers.first() {
+            Some((manager, _)) => (manager.to_string(), None, DetectionReason::Lockfile),
+            None => ("npm".to_string(), None, DetectionReason::Default),
+        },
+    };
 
     // Extraire les scripts définis dans le champ "scripts" de `package.json`
     let scripts_map = package_json_data
@@ -53,13 +75,766 @@ pub fn detect_package_manager_and_scripts(project_dir: &PathBuf) -> Option<Fetch
         })
         .unwrap_or_default();
 
-    // Log pour vérifier les scripts extraits
-    println!("Gestionnaire de paquets détecté: {}", detected_package_manager);
-    println!("Scripts extraits de package.json : {:?}", scripts_map);
-
     // Retourne le gestionnaire de paquets et les scripts extraits
-    Some(FetchPackageJson {
+    FetchPackageJson {
         manager: detected_package_manager,
         scripts: scripts_map,
-    })
+        version,
+        reason,
+        warnings,
+    }
+}
+
+/// Analyse le champ "packageManager" de package.json, au format `nom@version[+hash]`.
+fn parse_package_manager_field(package_json_data: &Value) -> Option<(String, Option<String>)> {
+    let field = package_json_data.get("packageManager")?.as_str()?.trim();
+    let (name, version) = match field.split_once('@') {
+        Some((name, version)) => (name, Some(version)),
+        None => (field, None),
+    };
+
+    if !KNOWN_PACKAGE_MANAGERS.contains(&name) {
+        return None;
+    }
+
+    let version = version
+        .map(|v| v.split('+').next().unwrap_or(v).to_string())
+        .filter(|v| !v.is_empty());
+    Some((name.to_string(), version))
+}
+
+/// Construit une tâche à partir d'un programme et de ses arguments.
+fn task(name: &str, source: &str, program: &str, args: &[&str]) -> Task {
+    Task {
+        name: name.to_string(),
+        source: source.to_string(),
+        command: std::iter::once(program).chain(args.iter().copied()).collect::<Vec<_>>().join(" "),
+        program: program.to_string(),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+        description: None,
+    }
+}
+
+/// Construit une tâche exécutant une ligne de commande via le shell du système.
+fn shell_task(name: &str, source: &str, command_line: &str) -> Task {
+    let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+    let mut shell_task = task(name, source, shell, &[flag, command_line]);
+    shell_task.command = command_line.to_string();
+    shell_task
+}
+
+/// Liste toutes les tâches exécutables d'un projet, toutes sources confondues.
+/// `package_json` est le contenu déjà lu de package.json, s'il existe.
+pub fn detect_tasks(project_dir: &Path, package_json: Option<&Value>) -> Vec<Task> {
+    let mut tasks = Vec::new();
+    tasks.extend(node_tasks(project_dir, package_json));
+    tasks.extend(cargo_tasks(project_dir));
+    tasks.extend(python_tasks(project_dir));
+    tasks.extend(go_tasks(project_dir));
+    tasks.extend(composer_tasks(project_dir));
+    tasks.extend(ruby_tasks(project_dir));
+    tasks.extend(make_tasks(project_dir));
+    tasks.extend(just_tasks(project_dir));
+    tasks.extend(taskfile_tasks(project_dir));
+    tasks.extend(procfile_tasks(project_dir));
+    tasks
+}
+
+/// Retrouve une tâche d'un projet par son nom et, si elle est précisée, sa source ;
+/// sans source, la première tâche du même nom.
+pub fn find_task(project_dir: &Path, source: Option<&str>, name: &str) -> Option<Task> {
+    detect_tasks(project_dir, read_package_json(project_dir).as_ref())
+        .into_iter()
+        .find(|task| task.name == name && source.iter().all(|source| *source == task.source))
+}
+
+/// Scripts de package.json, lancés via le gestionnaire de paquets détecté.
+fn node_tasks(project_dir: &Path, package_json: Option<&Value>) -> Vec<Task> {
+    let Some(package_json) = package_json else {
+        return Vec::new();
+    };
+    let package_info = detect_from_package_json(project_dir, package_json);
+
+    let mut tasks: Vec<Task> = package_info
+        .scripts
+        .into_iter()
+        .map(|(name, command)| Task {
+            args: vec!["run".to_string(), name.clone()],
+            name,
+            source: "package.json".to_string(),
+            command,
+            program: package_info.manager.clone(),
+            description: None,
+        })
+        .collect();
+    tasks.sort_by(|a, b| a.name.cmp(&b.name));
+    tasks
+}
+
+/// Commandes cargo courantes et alias définis dans `.cargo/config.toml`.
+fn cargo_tasks(project_dir: &Path) -> Vec<Task> {
+    if !project_dir.join("Cargo.toml").is_file() {
+        return Vec::new();
+    }
+
+    let mut tasks: Vec<Task> = ["run", "build", "test", "check"]
+        .iter()
+        .map(|command| task(command, "Cargo.toml", "cargo", &[command]))
+        .collect();
+
+    let cargo_config = ["config.toml", "config"]
+        .iter()
+        .find_map(|file| read_toml(&project_dir.join(".cargo").join(file)));
+    if let Some(aliases) = cargo_config.as_ref().and_then(|config| config.get("alias")?.as_table()) {
+        for (alias, expansion) in aliases {
+            let expansion = match expansion {
+                toml::Value::String(command) => command.clone(),
+                toml::Value::Array(parts) => parts
+                    .iter()
+                    .filter_map(toml::Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                _ => continue,
+            };
+            let mut alias_task = task(alias, "Cargo.toml", "cargo", &[alias]);
+            alias_task.command = format!("cargo {}", expansion);
+            tasks.push(alias_task);
+        }
+    }
+    tasks
+}
+
+/// Scripts Poetry, PDM ou uv de pyproject.toml, et serveur Django si `manage.py` est présent.
+/// Sans gestionnaire détecté, les scripts sont lancés avec l'interpréteur `python`.
+fn python_tasks(project_dir: &Path) -> Vec<Task> {
+    let pyproject = read_toml(&project_dir.join("pyproject.toml"));
+    let tool = |name: &str| pyproject.as_ref().and_then(|p| p.get("tool")?.get(name).cloned());
+
+    let runner = if project_dir.join("poetry.lock").is_file() || tool("poetry").is_some() {
+        Some("poetry")
+    } else if project_dir.join("pdm.lock").is_file() || tool("pdm").is_some() {
+        Some("pdm")
+    } else if project_dir.join("uv.lock").is_file() || tool("uv").is_some() {
+        Some("uv")
+    } else {
+        None
+    };
+
+    let mut tasks = Vec::new();
+    let script_tables = [
+        tool("poetry").and_then(|poetry| poetry.get("scripts").cloned()),
+        tool("pdm").and_then(|pdm| pdm.get("scripts").cloned()),
+        pyproject.as_ref().and_then(|p| p.get("project")?.get("scripts").cloned()),
+    ];
+    for table in script_tables.iter().flatten().filter_map(toml::Value::as_table) {
+        for (name, entry_point) in table {
+            if tasks.iter().any(|t: &Task| &t.name == name) {
+                continue;
+            }
+            let script_task = match runner {
+                Some(runner) => task(name, "pyproject.toml", runner, &["run", name]),
+                None => {
+                    // Point d'entrée `module:fonction` appelé directement par l'interpréteur.
+                    let Some((module, function)) = entry_point.as_str().and_then(|e| e.split_once(':')) else {
+                        continue;
+                    };
+                    let code = format!("import sys, {0}; sys.exit({0}.{1}())", module.trim(), function.trim());
+                    let mut python_task = task(name, "pyproject.toml", "python", &["-c", &code]);
+                    python_task.command = format!("python -c \"{}\"", code);
+                    python_task
+                }
+            };
+            tasks.push(script_task);
+        }
+    }
+
+    if project_dir.join("manage.py").is_file() {
+        let runserver = match runner {
+            Some(runner) => task("runserver", "manage.py", runner, &["run", "python", "manage.py", "runserver"]),
+            None => task("runserver", "manage.py", "python", &["manage.py", "runserver"]),
+        };
+        tasks.push(runserver);
+    }
+    tasks
+}
+
+/// Commandes go courantes pour un module Go.
+fn go_tasks(project_dir: &Path) -> Vec<Task> {
+    if !project_dir.join("go.mod").is_file() {
+        return Vec::new();
+    }
+
+    vec![
+        task("run", "go.mod", "go", &["run", "."]),
+        task("build", "go.mod", "go", &["build", "./..."]),
+        task("test", "go.mod", "go", &["test", "./..."]),
+    ]
+}
+
+/// Scripts de composer.json et serveur Laravel si `artisan` est présent.
+fn composer_tasks(project_dir: &Path) -> Vec<Task> {
+    let mut tasks = Vec::new();
+
+    let composer_json = fs::read_to_string(project_dir.join("composer.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Value>(&contents).ok());
+    if let Some(scripts) = composer_json.as_ref().and_then(|c| c.get("scripts")?.as_object()) {
+        for (name, command) in scripts {
+            let mut composer_task = task(name, "composer.json", "composer", &["run-script", name]);
+            if let Some(command) = command.as_str() {
+                composer_task.command = command.to_string();
+            }
+            tasks.push(composer_task);
+        }
+    }
+
+    if project_dir.join("artisan").is_file() {
+        tasks.push(task("serve", "artisan", "php", &["artisan", "serve"]));
+    }
+    tasks
+}
+
+/// Tâches Rake du Rakefile et serveur Rails si `bin/rails` est présent.
+fn ruby_tasks(project_dir: &Path) -> Vec<Task> {
+    let has_gemfile = project_dir.join("Gemfile").is_file();
+    let mut tasks = Vec::new();
+
+    if let Ok(rakefile) = fs::read_to_string(project_dir.join("Rakefile")) {
+        for name in rakefile.lines().filter_map(rake_task_name) {
+            let rake_task = if has_gemfile {
+                task(&name, "Rakefile", "bundle", &["exec", "rake", &name])
+            } else {
+                task(&name, "Rakefile", "rake", &[&name])
+            };
+            tasks.push(rake_task);
+        }
+    }
+
+    if project_dir.join("bin").join("rails").is_file() {
+        tasks.push(task("server", "bin/rails", "bundle", &["exec", "rails", "server"]));
+    }
+    tasks
+}
+
+/// Extrait le nom d'une tâche Rake (`task :name`, `task name: [...]`, `task "name"`).
+fn rake_task_name(line: &str) -> Option<String> {
+    let rest = line.trim().strip_prefix("task")?;
+    if !rest.starts_with([' ', '(']) {
+        return None;
+    }
+
+    let rest = rest.trim_start_matches([' ', '(']);
+    let name: String = rest
+        .trim_start_matches([':', '"', '\''])
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':'))
+        .collect();
+    let name = name.trim_end_matches(':').to_string();
+    Some(name).filter(|name| !name.is_empty())
+}
+
+/// Fichiers de tâches indépendants du gestionnaire de paquets.
+pub const TASK_FILES: &[&str] = &[
+    "GNUmakefile",
+    "makefile",
+    "Makefile",
+    "justfile",
+    "Justfile",
+    ".justfile",
+    "Taskfile.yml",
+    "Taskfile.yaml",
+    "taskfile.yml",
+    "taskfile.yaml",
+    "Procfile",
+    "Procfile.dev",
+];
+
+/// Lit le premier fichier existant parmi plusieurs noms possibles.
+fn read_first_file(project_dir: &Path, file_names: &[&'static str]) -> Option<(&'static str, String)> {
+    file_names
+        .iter()
+        .find_map(|file_name| Some((*file_name, fs::read_to_string(project_dir.join(file_name)).ok()?)))
+}
+
+/// Extrait le texte d'un commentaire de description (`## Lance le serveur`).
+fn comment_description(line: &str) -> Option<String> {
+    let description = line.trim().strip_prefix('#')?.trim_start_matches('#').trim();
+    Some(description.to_string()).filter(|description| !description.is_empty())
+}
+
+/// Cibles d'un Makefile, décrites par un commentaire `## ...` en fin de ligne ou sur la ligne précédente.
+fn make_tasks(project_dir: &Path) -> Vec<Task> {
+    let Some((source, contents)) = read_first_file(project_dir, &["GNUmakefile", "makefile", "Makefile"]) else {
+        return Vec::new();
+    };
+
+    let mut tasks: Vec<Task> = Vec::new();
+    let mut pending_description = None;
+    for line in contents.lines() {
+        // Seul un commentaire en début de ligne décrit la cible qui le suit immédiatement.
+        if line.starts_with('#') {
+            pending_description = comment_description(line);
+            continue;
+        }
+        let description = pending_description.take();
+        if line.starts_with([' ', '\t', '.']) {
+            continue;
+        }
+
+        let (rule, inline_description) = match line.split_once("##") {
+            Some((rule, comment)) => (rule, Some(comment.trim().to_string()).filter(|c| !c.is_empty())),
+            None => (line, None),
+        };
+        // `cible: dépendances`, en excluant les affectations (`VAR := valeur`, `VAR ::= valeur`).
+        let Some((targets, rest)) = rule.split_once(':') else {
+            continue;
+        };
+        if rest.starts_with('=') || rest.starts_with(":=") || targets.contains(['=', '$', '%']) {
+            continue;
+        }
+
+        for target in targets.split_whitespace() {
+            if tasks.iter().any(|t| t.name == target) {
+                continue;
+            }
+            let mut make_task = task(target, source, "make", &[target]);
+            make_task.description = inline_description.clone().or_else(|| description.clone());
+            tasks.push(make_task);
+        }
+    }
+    tasks
+}
+
+/// Recettes publiques d'un justfile, décrites par le commentaire qui les précède immédiatement.
+/// Les recettes privées (`[private]` ou nom commençant par `_`) sont ignorées.
+fn just_tasks(project_dir: &Path) -> Vec<Task> {
+    let Some((source, contents)) = read_first_file(project_dir, &["justfile", "Justfile", ".justfile"]) else {
+        return Vec::new();
+    };
+
+    let mut tasks = Vec::new();
+    let mut pending_description = None;
+    let mut private = false;
+    for line in contents.lines() {
+        // Les commentaires indentés appartiennent au corps d'une recette.
+        if line.starts_with('#') {
+            pending_description = comment_description(line);
+            continue;
+        }
+        // Les attributs (`[private]`, `[group(...)]`) se placent entre le commentaire et la recette.
+        if let Some(attributes) = line.strip_prefix('[') {
+            private |= attributes
+                .trim_end()
+                .trim_end_matches(']')
+                .split(',')
+                .any(|attribute| attribute.trim() == "private");
+            continue;
+        }
+        let description = pending_description.take();
+        let is_private = std::mem::take(&mut private);
+        if line.starts_with([' ', '\t']) || line.trim().is_empty() {
+            continue;
+        }
+
+        let first_word = line.split_whitespace().next().unwrap_or_default();
+        if matches!(first_word, "set" | "alias" | "export" | "import" | "mod") {
+            continue;
+        }
+        let Some((header, rest)) = line.split_once(':') else {
+            continue;
+        };
+        if rest.starts_with('=') {
+            continue;
+        }
+
+        let name = header
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches('@');
+        if name.is_empty() || name.starts_with('_') || is_private {
+            continue;
+        }
+        let mut just_task = task(name, source, "just", &[name]);
+        just_task.description = description;
+        tasks.push(just_task);
+    }
+    tasks
+}
+
+/// Tâches d'un Taskfile (go-task) avec leur champ `desc`.
+fn taskfile_tasks(project_dir: &Path) -> Vec<Task> {
+    let Some((source, contents)) = read_first_file(
+        project_dir,
+        &["Taskfile.yml", "Taskfile.yaml", "taskfile.yml", "taskfile.yaml"],
+    ) else {
+        return Vec::new();
+    };
+    let Ok(taskfile) = serde_yaml::from_str::<serde_yaml::Value>(&contents) else {
+        return Vec::new();
+    };
+    let Some(definitions) = taskfile.get("tasks").and_then(serde_yaml::Value::as_mapping) else {
+        return Vec::new();
+    };
+
+    definitions
+        .iter()
+        .filter_map(|(name, definition)| {
+            let name = name.as_str()?;
+            if definition.get("internal").and_then(serde_yaml::Value::as_bool) == Some(true) {
+                return None;
+            }
+            let mut go_task = task(name, source, "task", &[name]);
+            go_task.description = ["desc", "summary"]
+                .iter()
+                .find_map(|field| definition.get(field)?.as_str())
+                .map(|description| description.trim().to_string());
+            Some(go_task)
+        })
+        .collect()
+}
+
+/// Processus déclarés dans le Procfile et le Procfile.dev (`web: commande`), lancés via le shell.
+fn procfile_tasks(project_dir: &Path) -> Vec<Task> {
+    ["Procfile", "Procfile.dev"]
+        .iter()
+        .filter_map(|source| Some((*source, fs::read_to_string(project_dir.join(source)).ok()?)))
+        .flat_map(|(source, contents)| {
+            contents
+                .lines()
+                .filter(|line| !line.trim_start().starts_with('#'))
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, command_line)| (name.trim(), command_line.trim()))
+                .filter(|(name, command_line)| {
+                    !name.is_empty()
+                        && !command_line.is_empty()
+                        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+                })
+                .map(|(name, command_line)| shell_task(name, source, command_line))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn project_with(files: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in files {
+            fs::write(dir.path().join(file), "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn detects_manager_from_lockfile() {
+        let dir = project_with(&["pnpm-lock.yaml"]);
+        let info = detect_from_package_json(dir.path(), &json!({ "scripts": { "dev": "vite" } }));
+        assert_eq!(info.manager, "pnpm");
+        assert_eq!(info.reason, DetectionReason::Lockfile);
+        assert_eq!(info.scripts.get("dev").map(String::as_str), Some("vite"));
+        assert!(info.warnings.is_empty());
+    }
+
+    #[test]
+    fn defaults_to_npm_without_lockfile() {
+        let dir = project_with(&[]);
+        let info = detect_from_package_json(dir.path(), &json!({}));
+        assert_eq!((info.manager.as_str(), info.reason), ("npm", DetectionReason::Default));
+    }
+
+    #[test]
+    fn package_manager_field_takes_precedence() {
+        let dir = project_with(&["yarn.lock"]);
+        let info = detect_from_package_json(dir.path(), &json!({ "packageManager": "pnpm@9.1.0+sha512.abc" }));
+        assert_eq!(info.manager, "pnpm");
+        assert_eq!(info.version.as_deref(), Some("9.1.0"));
+        assert_eq!(info.reason, DetectionReason::PackageManagerField);
+        assert_eq!(info.warnings, ["Le champ packageManager indique pnpm mais le fichier de lock est yarn.lock"]);
+    }
+
+    #[test]
+    fn reports_conflicting_lockfiles() {
+        let dir = project_with(&["yarn.lock", "package-lock.json"]);
+        let info = detect_from_package_json(dir.path(), &json!({}));
+        assert_eq!(info.manager, "yarn");
+        assert_eq!(info.warnings, ["Fichiers de lock en conflit : yarn.lock, package-lock.json"]);
+    }
+
+    #[test]
+    fn lockfiles_of_the_same_manager_do_not_conflict() {
+        let dir = project_with(&["bun.lockb", "bun.lock"]);
+        let info = detect_from_package_json(dir.path(), &json!({ "packageManager": "bun@1.1.0" }));
+        assert_eq!(info.manager, "bun");
+        assert!(info.warnings.is_empty());
+    }
+
+    #[test]
+    fn ignores_unknown_package_manager_field() {
+        let dir = project_with(&["package-lock.json"]);
+        let info = detect_from_package_json(dir.path(), &json!({ "packageManager": "deno@2.0.0" }));
+        assert_eq!((info.manager.as_str(), info.reason), ("npm", DetectionReason::Lockfile));
+    }
+
+    fn project_file(file_name: &str, contents: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(file_name), contents).unwrap();
+        dir
+    }
+
+    fn names_and_descriptions(tasks: &[Task]) -> Vec<(&str, Option<&str>)> {
+        tasks.iter().map(|task| (task.name.as_str(), task.description.as_deref())).collect()
+    }
+
+    #[test]
+    fn parses_makefile_targets() {
+        let dir = project_file(
+            "Makefile",
+            "CC := gcc\n\
+             VERSION ::= 1.0\n\
+             .PHONY: build test\n\
+             \n\
+             ## Compile le projet\n\
+             build: deps\n\
+             \t# commentaire de recette\n\
+             \t$(CC) main.c\n\
+             test: build ## Lance les tests\n\
+             \t./run-tests\n\
+             %.o: %.c\n\
+             \t$(CC) -c $<\n\
+             # Sans rapport\n\
+             \n\
+             clean lint:\n\
+             \trm -rf out\n",
+        );
+        let tasks = make_tasks(dir.path());
+        assert_eq!(
+            names_and_descriptions(&tasks),
+            [
+                ("build", Some("Compile le projet")),
+                ("test", Some("Lance les tests")),
+                ("clean", None),
+                ("lint", None),
+            ]
+        );
+        assert_eq!((tasks[0].program.as_str(), tasks[0].args.as_slice()), ("make", ["build".to_string()].as_slice()));
+    }
+
+    #[test]
+    fn parses_justfile_recipes() {
+        let dir = project_file(
+            "justfile",
+            "set dotenv-load\n\
+             alias b := build\n\
+             version := \"1.0\"\n\
+             \n\
+             # Compile le projet\n\
+             [group('dev')]\n\
+             build target='debug':\n\
+             \x20   # commentaire de recette\n\
+             \x20   cargo build\n\
+             test: build\n\
+             \x20   cargo test\n\
+             \n\
+             # Description orpheline\n\
+             \n\
+             @serve port=\"8080\":\n\
+             \x20   ./serve {{port}}\n\
+             \n\
+             # Recette interne\n\
+             [private]\n\
+             setup:\n\
+             \x20   ./setup.sh\n\
+             [no-cd, private]\n\
+             cleanup:\n\
+             \x20   rm -rf out\n\
+             _helper:\n\
+             \x20   echo aide\n",
+        );
+        let tasks = just_tasks(dir.path());
+        assert_eq!(
+            names_and_descriptions(&tasks),
+            [("build", Some("Compile le projet")), ("test", None), ("serve", None)]
+        );
+        assert_eq!(tasks[2].args, ["serve"]);
+    }
+
+    #[test]
+    fn parses_taskfile_tasks() {
+        let dir = project_file(
+            "Taskfile.yml",
+            "version: '3'\n\
+             tasks:\n\
+             \x20 build:\n\
+             \x20   desc: Compile le projet\n\
+             \x20   cmds: [go build ./...]\n\
+             \x20 lint:\n\
+             \x20   summary: |\n\
+             \x20     Analyse le code\n\
+             \x20   cmds: [golangci-lint run]\n\
+             \x20 setup:\n\
+             \x20   internal: true\n\
+             \x20   cmds: [go mod download]\n",
+        );
+        let tasks = taskfile_tasks(dir.path());
+        assert_eq!(
+            names_and_descriptions(&tasks),
+            [("build", Some("Compile le projet")), ("lint", Some("Analyse le code"))]
+        );
+        assert_eq!((tasks[0].program.as_str(), tasks[0].source.as_str()), ("task", "Taskfile.yml"));
+    }
+
+    #[test]
+    fn reads_procfile_and_procfile_dev() {
+        let dir = project_file("Procfile", "# production\nweb: bundle exec puma -C config/puma.rb\nworker: bundle exec sidekiq\n");
+        fs::write(dir.path().join("Procfile.dev"), "web: bin/rails server -p 3000\ncss: bin/rails tailwindcss:watch\n").unwrap();
+        let tasks = procfile_tasks(dir.path());
+        assert_eq!(
+            tasks
+                .iter()
+                .map(|task| (task.source.as_str(), task.name.as_str(), task.command.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                ("Procfile", "web", "bundle exec puma -C config/puma.rb"),
+                ("Procfile", "worker", "bundle exec sidekiq"),
+                ("Procfile.dev", "web", "bin/rails server -p 3000"),
+                ("Procfile.dev", "css", "bin/rails tailwindcss:watch"),
+            ]
+        );
+    }
+
+    fn project_files(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, contents) in files {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    fn commands(tasks: &[Task]) -> Vec<(&str, &str)> {
+        tasks.iter().map(|task| (task.name.as_str(), task.command.as_str())).collect()
+    }
+
+    #[test]
+    fn node_tasks_use_the_given_package_json() {
+        let dir = project_files(&[("yarn.lock", "")]);
+        let package_json = json!({ "scripts": { "dev": "vite", "build": "vite build" } });
+        let tasks = node_tasks(dir.path(), Some(&package_json));
+        assert_eq!(commands(&tasks), [("build", "vite build"), ("dev", "vite")]);
+        assert_eq!((tasks[1].program.as_str(), tasks[1].args.as_slice()), ("yarn", ["run".to_string(), "dev".to_string()].as_slice()));
+        assert!(node_tasks(dir.path(), None).is_empty());
+    }
+
+    #[test]
+    fn cargo_tasks_include_config_aliases() {
+        let dir = project_files(&[
+            ("Cargo.toml", "[package]\nname = \"demo\"\n"),
+            (".cargo/config.toml", "[alias]\nxtask = \"run --package xtask --\"\nci = [\"clippy\", \"--all-targets\"]\n"),
+        ]);
+        assert_eq!(
+            commands(&cargo_tasks(dir.path())),
+            [
+                ("run", "cargo run"),
+                ("build", "cargo build"),
+                ("test", "cargo test"),
+                ("check", "cargo check"),
+                ("ci", "cargo clippy --all-targets"),
+                ("xtask", "cargo run --package xtask --"),
+            ]
+        );
+        assert!(cargo_tasks(project_files(&[]).path()).is_empty());
+    }
+
+    #[test]
+    fn python_tasks_use_poetry() {
+        let dir = project_files(&[
+            ("pyproject.toml", "[tool.poetry]\nname = \"demo\"\n\n[tool.poetry.scripts]\nserve = \"demo.app:main\"\n"),
+            ("manage.py", ""),
+        ]);
+        assert_eq!(
+            commands(&python_tasks(dir.path())),
+            [("serve", "poetry run serve"), ("runserver", "poetry run python manage.py runserver")]
+        );
+    }
+
+    #[test]
+    fn python_tasks_use_pdm_lockfile() {
+        let dir = project_files(&[
+            ("pyproject.toml", "[project]\nname = \"demo\"\n\n[project.scripts]\nserve = \"demo.app:main\"\n"),
+            ("pdm.lock", ""),
+        ]);
+        assert_eq!(commands(&python_tasks(dir.path())), [("serve", "pdm run serve")]);
+    }
+
+    #[test]
+    fn python_tasks_use_uv_only_when_configured() {
+        let pyproject = "[project]\nname = \"demo\"\n\n[project.scripts]\nserve = \"demo.app:main\"\n";
+        let locked = project_files(&[("pyproject.toml", pyproject), ("uv.lock", "")]);
+        assert_eq!(commands(&python_tasks(locked.path())), [("serve", "uv run serve")]);
+
+        let configured = project_files(&[("pyproject.toml", &format!("{}\n[tool.uv]\ndev-dependencies = []\n", pyproject))]);
+        assert_eq!(commands(&python_tasks(configured.path())), [("serve", "uv run serve")]);
+    }
+
+    #[test]
+    fn python_tasks_fall_back_to_python() {
+        let dir = project_files(&[
+            ("pyproject.toml", "[project]\nname = \"demo\"\n\n[project.scripts]\nserve = \"demo.app:main\"\n"),
+            ("manage.py", ""),
+        ]);
+        let tasks = python_tasks(dir.path());
+        assert_eq!(tasks[0].program, "python");
+        assert_eq!(tasks[0].args, ["-c", "import sys, demo.app; sys.exit(demo.app.main())"]);
+        assert_eq!(tasks[1].command, "python manage.py runserver");
+    }
+
+    #[test]
+    fn go_tasks_require_go_mod() {
+        let dir = project_files(&[("go.mod", "module example.com/demo\n")]);
+        assert_eq!(
+            commands(&go_tasks(dir.path())),
+            [("run", "go run ."), ("build", "go build ./..."), ("test", "go test ./...")]
+        );
+        assert!(go_tasks(project_files(&[]).path()).is_empty());
+    }
+
+    #[test]
+    fn composer_tasks_include_scripts_and_artisan() {
+        let dir = project_files(&[
+            ("composer.json", r#"{ "scripts": { "test": "phpunit", "lint": ["@phpcs", "@phpstan"] } }"#),
+            ("artisan", ""),
+        ]);
+        let tasks = composer_tasks(dir.path());
+        assert_eq!(
+            commands(&tasks),
+            [("lint", "composer run-script lint"), ("test", "phpunit"), ("serve", "php artisan serve")]
+        );
+        assert_eq!(tasks[1].args, ["run-script", "test"]);
+    }
+
+    #[test]
+    fn ruby_tasks_use_bundle_exec_with_gemfile() {
+        let rakefile = "task :default => :test\ntask \"build\" do\nend\ntask(:lint) { }\n";
+        let bundled = project_files(&[("Rakefile", rakefile), ("Gemfile", ""), ("bin/rails", "")]);
+        assert_eq!(
+            commands(&ruby_tasks(bundled.path())),
+            [
+                ("default", "bundle exec rake default"),
+                ("build", "bundle exec rake build"),
+                ("lint", "bundle exec rake lint"),
+                ("server", "bundle exec rails server"),
+            ]
+        );
+
+        let standalone = project_files(&[("Rakefile", rakefile)]);
+        assert_eq!(
+            commands(&ruby_tasks(standalone.path())),
+            [("default", "rake default"), ("build", "rake build"), ("lint", "rake lint")]
+        );
+    }
 }
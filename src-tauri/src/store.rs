@@ -0,0 +1,273 @@
+// src-tauri/src/store.rs
+
+use crate::config::{get_config_file_path, load_or_initialize_config, save_config, DevLauncherConfig};
+use crate::probes::ScriptHealth;
+use crate::profiles::LaunchProfile;
+use crate::queue::ConcurrencyLimits;
+use crate::types::{ProjectConfig, ProjectStatus, RunningProcess};
+use log::error;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::thread;
+use std::time::Duration;
+
+/// Délai pendant lequel les écritures successives sont regroupées avant la sauvegarde.
+const PERSIST_DEBOUNCE: Duration = Duration::from_millis(250);
+/// Délai maximal d'attente de la dernière sauvegarde à la fermeture de l'application.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Fonction appelée lorsqu'une sauvegarde de la configuration échoue.
+type PersistErrorHandler = Box<dyn Fn(String) + Send>;
+
+/// Message adressé à la tâche d'écriture différée.
+enum PersistMessage {
+    Save(DevLauncherConfig),
+    /// Écrit immédiatement la configuration en attente, puis le signale.
+    Flush(Sender<()>),
+}
+
+/// Magasin d'état en mémoire : configuration des projets, processus en cours
+/// et persistance différée de la configuration sur le disque.
+pub struct StateStore {
+    config: RwLock<DevLauncherConfig>,
+    processes: Mutex<HashMap<String, RunningProcess>>,
+    persist_tx: Sender<PersistMessage>,
+    /// Raison pour laquelle la configuration ne peut pas être modifiée : un fichier illisible
+    /// qui n'a pas pu être copié ne doit pas être écrasé.
+    read_only: Option<String>,
+    on_persist_error: Arc<Mutex<Option<PersistErrorHandler>>>,
+}
+
+/// Instantané de l'état de l'application, utilisé pour le débogage.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppStateSnapshot {
+    pub config_path: String,
+    pub projects: Vec<ProjectSnapshot>,
+    pub processes: Vec<RunningProcess>,
+}
+
+/// Instantané d'un projet avec son statut d'exécution.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectSnapshot {
+    #[serde(flatten)]
+    pub config: ProjectConfig,
+    pub status: ProjectStatus,
+}
+
+impl StateStore {
+    /// Charge la configuration depuis le disque et démarre la tâche d'écriture différée.
+    pub fn load() -> Self {
+        let (persist_tx, persist_rx) = mpsc::channel();
+        let on_persist_error: Arc<Mutex<Option<PersistErrorHandler>>> = Arc::default();
+        let handler = Arc::clone(&on_persist_error);
+        thread::spawn(move || persist_loop(persist_rx, handler));
+
+        let (config, read_only) = load_or_initialize_config();
+        if let Some(reason) = &read_only {
+            error!("{}", reason);
+        }
+        StateStore {
+            config: RwLock::new(config),
+            processes: Mutex::new(HashMap::new()),
+            persist_tx,
+            read_only,
+            on_persist_error,
+        }
+    }
+
+    /// Définit la fonction appelée lorsqu'une sauvegarde de la configuration échoue.
+    pub fn on_persist_error(&self, handler: impl Fn(String) + Send + 'static) {
+        *self.on_persist_error.lock().unwrap_or_else(PoisonError::into_inner) = Some(Box::new(handler));
+    }
+
+    /// Écrit sans attendre la configuration en attente de sauvegarde, avant la fermeture de l'application.
+    pub fn flush(&self) {
+        let (done_tx, done_rx) = mpsc::channel();
+        if self.persist_tx.send(PersistMessage::Flush(done_tx)).is_ok() && done_rx.recv_timeout(FLUSH_TIMEOUT).is_err() {
+            error!("La sauvegarde de la configuration n'a pas pu être terminée avant la fermeture.");
+        }
+    }
+
+    /// Retourne la liste des projets enregistrés.
+    pub fn projects(&self) -> Vec<ProjectConfig> {
+        self.config
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .project_folders
+            .clone()
+    }
+
+    /// Retourne un projet par son ID.
+    pub fn project(&self, id: &str) -> Option<ProjectConfig> {
+        self.config
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .project_folders
+            .iter()
+            .find(|project| project.id == id)
+            .cloned()
+    }
+
+    /// Retourne la liste des profils de lancement.
+    pub fn profiles(&self) -> Vec<LaunchProfile> {
+        self.config
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .profiles
+            .clone()
+    }
+
+    /// Retourne un profil de lancement par son ID.
+    pub fn profile(&self, id: &str) -> Option<LaunchProfile> {
+        self.config
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .profiles
+            .iter()
+            .find(|profile| profile.id == id)
+            .cloned()
+    }
+
+    /// Retourne les limites de scripts exécutés simultanément.
+    pub fn concurrency(&self) -> ConcurrencyLimits {
+        self.config
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .concurrency
+            .clone()
+    }
+
+    /// Modifie la configuration puis planifie sa sauvegarde sur le disque.
+    /// Échoue sans rien modifier si la configuration ne peut pas être sauvegardée.
+    pub fn update<T>(&self, f: impl FnOnce(&mut DevLauncherConfig) -> T) -> Result<T, String> {
+        if let Some(reason) = &self.read_only {
+            return Err(reason.clone());
+        }
+        let mut config = self.config.write().unwrap_or_else(PoisonError::into_inner);
+        let result = f(&mut config);
+        if self.persist_tx.send(PersistMessage::Save(config.clone())).is_err() {
+            error!("La tâche de sauvegarde de la configuration est arrêtée.");
+            return Err("La configuration ne peut plus être sauvegardée.".to_string());
+        }
+        Ok(result)
+    }
+
+    /// Enregistre un processus lancé.
+    pub fn register_process(&self, process: RunningProcess) {
+        self.processes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(process.run_id.clone(), process);
+    }
+
+    /// Retire un processus terminé du registre.
+    pub fn unregister_process(&self, run_id: &str) -> Option<RunningProcess> {
+        self.processes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(run_id)
+    }
+
+    /// Retourne un processus en cours par son identifiant d'exécution.
+    pub fn process(&self, run_id: &str) -> Option<RunningProcess> {
+        self.processes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(run_id)
+            .cloned()
+    }
+
+    /// Modifie un processus en cours (PID après un redémarrage, demande d'arrêt, ...).
+    pub fn update_process<T>(&self, run_id: &str, f: impl FnOnce(&mut RunningProcess) -> T) -> Option<T> {
+        self.processes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(run_id)
+            .map(f)
+    }
+
+    /// Retourne les processus en cours.
+    pub fn processes(&self) -> Vec<RunningProcess> {
+        self.processes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Calcule le statut d'exécution d'un projet.
+    pub fn project_status(&self, id: &str) -> ProjectStatus {
+        let processes = self.processes.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut project_processes = processes.values().filter(|process| process.project_id == id).peekable();
+
+        if project_processes.peek().is_none() {
+            ProjectStatus::Idle
+        } else if project_processes.any(|process| process.health == Some(ScriptHealth::Unhealthy)) {
+            ProjectStatus::Unhealthy
+        } else {
+            ProjectStatus::Running
+        }
+    }
+
+    /// Construit un instantané complet de l'état.
+    pub fn snapshot(&self) -> AppStateSnapshot {
+        let projects = self
+            .projects()
+            .into_iter()
+            .map(|config| {
+                let status = self.project_status(&config.id);
+                ProjectSnapshot { config, status }
+            })
+            .collect();
+
+        AppStateSnapshot {
+            config_path: get_config_file_path().to_string_lossy().to_string(),
+            projects,
+            processes: self.processes(),
+        }
+    }
+}
+
+/// Sauvegarde la configuration en regroupant les modifications rapprochées.
+fn persist_loop(persist_rx: Receiver<PersistMessage>, on_error: Arc<Mutex<Option<PersistErrorHandler>>>) {
+    let save = |config: &DevLauncherConfig| {
+        if let Err(e) = save_config(config) {
+            let message = format!("Erreur lors de la sauvegarde de la configuration : {}", e);
+            error!("{}", message);
+            if let Some(handler) = on_error.lock().unwrap_or_else(PoisonError::into_inner).as_ref() {
+                handler(message);
+            }
+        }
+    };
+
+    while let Ok(message) = persist_rx.recv() {
+        let mut latest = match message {
+            PersistMessage::Save(config) => config,
+            PersistMessage::Flush(done_tx) => {
+                let _ = done_tx.send(());
+                continue;
+            }
+        };
+        loop {
+            match persist_rx.recv_timeout(PERSIST_DEBOUNCE) {
+                Ok(PersistMessage::Save(newer)) => latest = newer,
+                Ok(PersistMessage::Flush(done_tx)) => {
+                    save(&latest);
+                    let _ = done_tx.send(());
+                    break;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    save(&latest);
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    save(&latest);
+                    return;
+                }
+            }
+        }
+    }
+}
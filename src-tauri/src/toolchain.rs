@@ -0,0 +1,174 @@
+// src-tauri/src/toolchain.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, PoisonError};
+
+/// Gestionnaires de paquets pouvant être proposés en remplacement.
+const PACKAGE_MANAGERS: &[&str] = &["npm", "pnpm", "yarn", "bun"];
+
+/// Informations sur un exécutable installé.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub path: String,
+    pub version: Option<String>,
+}
+
+/// Rapport de disponibilité du gestionnaire de paquets d'un projet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolchainReport {
+    pub manager: String,
+    pub installed: Option<ToolInfo>,
+    pub alternatives: Vec<ToolInfo>,
+    pub suggestion: Option<String>,
+}
+
+/// Cache des exécutables résolus, pour éviter de relancer `--version` à chaque appel.
+/// Les exécutables introuvables ne sont pas mis en cache : un outil installé entre-temps est détecté au prochain appel.
+#[derive(Default)]
+pub struct ToolchainCache {
+    entries: Mutex<HashMap<String, ToolInfo>>,
+}
+
+impl ToolchainCache {
+    /// Résout un exécutable et sa version, en utilisant le cache si l'exécutable est toujours présent.
+    pub fn probe(&self, name: &str) -> Option<ToolInfo> {
+        let cached = self
+            .entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned();
+        if let Some(cached) = cached.filter(|info| is_executable(Path::new(&info.path))) {
+            return Some(cached);
+        }
+
+        let Some(path) = resolve_binary(name) else {
+            self.entries.lock().unwrap_or_else(PoisonError::into_inner).remove(name);
+            return None;
+        };
+        let info = ToolInfo {
+            name: name.to_string(),
+            version: probe_version(&path),
+            path: path.to_string_lossy().to_string(),
+        };
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.to_string(), info.clone());
+        Some(info)
+    }
+
+    /// Construit le rapport de disponibilité pour un gestionnaire de paquets.
+    pub fn report(&self, manager: &str) -> ToolchainReport {
+        let installed = self.probe(manager);
+        let alternatives: Vec<ToolInfo> = PACKAGE_MANAGERS
+            .iter()
+            .filter(|name| **name != manager)
+            .filter_map(|name| self.probe(name))
+            .collect();
+
+        let suggestion = if installed.is_some() {
+            None
+        } else if matches!(manager, "pnpm" | "yarn") && self.probe("corepack").is_some() {
+            Some(format!("{} n'est pas installé : exécutez `corepack enable {}`.", manager, manager))
+        } else if let Some(alternative) = alternatives.first() {
+            Some(format!(
+                "{} n'est pas installé : utilisez {} à la place ou installez {}.",
+                manager, alternative.name, manager
+            ))
+        } else {
+            Some(format!("{} n'est pas installé.", manager))
+        };
+
+        ToolchainReport {
+            manager: manager.to_string(),
+            installed,
+            alternatives,
+            suggestion,
+        }
+    }
+}
+
+/// Dossiers d'installation courants, consultés après le PATH.
+fn extra_search_dirs() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+
+    let mut search_dirs = vec![
+        home.join(".bun").join("bin"),
+        home.join(".local").join("share").join("pnpm"),
+        home.join("Library").join("pnpm"),
+        home.join(".volta").join("bin"),
+        home.join(".local").join("share").join("fnm").join("aliases").join("default").join("bin"),
+        home.join(".fnm").join("aliases").join("default").join("bin"),
+        home.join(".yarn").join("bin"),
+    ];
+    if let Ok(pnpm_home) = env::var("PNPM_HOME") {
+        search_dirs.insert(0, PathBuf::from(pnpm_home));
+    }
+    if let Ok(volta_home) = env::var("VOLTA_HOME") {
+        search_dirs.insert(0, PathBuf::from(volta_home).join("bin"));
+    }
+    search_dirs
+}
+
+/// Noms de fichiers possibles d'un exécutable selon la plateforme.
+fn binary_file_names(name: &str) -> Vec<String> {
+    if cfg!(windows) {
+        vec![format!("{}.exe", name), format!("{}.cmd", name), name.to_string()]
+    } else {
+        vec![name.to_string()]
+    }
+}
+
+/// Recherche un exécutable dans le PATH puis dans les dossiers d'installation courants.
+pub fn resolve_binary(name: &str) -> Option<PathBuf> {
+    let path_dirs = env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    path_dirs
+        .into_iter()
+        .chain(extra_search_dirs())
+        .flat_map(|dir| {
+            binary_file_names(name)
+                .into_iter()
+                .map(move |file_name| dir.join(file_name))
+        })
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Lance `<exécutable> --version` et retourne la version affichée.
+fn probe_version(path: &Path) -> Option<String> {
+    let output = Command::new(path).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let version = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()?
+        .trim()
+        .trim_start_matches('v')
+        .to_string();
+    Some(version).filter(|v| !v.is_empty())
+}
@@ -0,0 +1,509 @@
+// src-tauri/src/triggers.rs
+
+use crate::queue::submit;
+use crate::runner::{request_restart, LaunchRequest, RunTrigger};
+use crate::script::find_task;
+use crate::types::{split_script_settings_key, AppState, ProjectConfig};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::Gitignore;
+use ignore::WalkBuilder;
+use log::{error, info, warn};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+fn default_debounce_ms() -> u64 {
+    500
+}
+
+/// Effet d'un changement de fichier sur le script surveillé.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchAction {
+    /// Redémarre le script s'il est en cours, sans effet sinon (serveurs).
+    #[default]
+    Restart,
+    /// Redémarre le script s'il est en cours, le lance sinon (builds, tests).
+    Rerun,
+}
+
+/// Relance d'un script lorsque des fichiers de son projet changent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchTrigger {
+    /// Motifs des fichiers surveillés, relatifs au projet (`src/**`) ; tout le projet à défaut.
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+    #[serde(default)]
+    pub action: WatchAction,
+}
+
+/// Déclencheur d'un script, avec ses motifs compilés et les `.gitignore` de son projet.
+struct TriggerSpec {
+    project_id: String,
+    /// Clé des réglages du script (source et nom).
+    key: String,
+    root: PathBuf,
+    include: GlobSet,
+    exclude: GlobSet,
+    gitignores: Vec<Gitignore>,
+    debounce: Duration,
+    action: WatchAction,
+}
+
+impl TriggerSpec {
+    fn build(project: &ProjectConfig, key: &str, trigger: &WatchTrigger) -> Result<Self, String> {
+        let root = PathBuf::from(&project.path);
+        Ok(TriggerSpec {
+            project_id: project.id.clone(),
+            key: key.to_string(),
+            include: glob_set(&trigger.include)?,
+            exclude: glob_set(&trigger.exclude)?,
+            gitignores: find_gitignores(&root),
+            root,
+            debounce: Duration::from_millis(trigger.debounce_ms),
+            action: trigger.action,
+        })
+    }
+
+    /// Dossiers de base des motifs du déclencheur : tout le projet sans motif d'inclusion.
+    fn bases(&self, include: &[String]) -> Vec<PathBuf> {
+        if include.is_empty() {
+            return vec![self.root.clone()];
+        }
+        include.iter().map(|pattern| glob_base(&self.root, pattern)).collect()
+    }
+
+    /// Indique si un dossier créé après le chargement doit être surveillé.
+    fn watches_dir(&self, dir: &Path) -> bool {
+        dir.starts_with(&self.root)
+            && !dir.components().any(|component| component == Component::Normal(".git".as_ref()))
+            && !self.gitignores.iter().any(|gitignore| {
+                dir.starts_with(gitignore.path()) && gitignore.matched_path_or_any_parents(dir, true).is_ignore()
+            })
+    }
+
+    /// Indique si la modification d'un fichier concerne ce déclencheur.
+    fn matches(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        if relative.components().any(|component| component == Component::Normal(".git".as_ref())) {
+            return false;
+        }
+        if !self.include.is_empty() && !self.include.is_match(relative) {
+            return false;
+        }
+        if self.exclude.is_match(relative) {
+            return false;
+        }
+        !self.gitignores.iter().any(|gitignore| {
+            path.starts_with(gitignore.path())
+                && gitignore
+                    .matched_path_or_any_parents(path, path.is_dir())
+                    .is_ignore()
+        })
+    }
+}
+
+fn glob_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).map_err(|e| format!("Motif invalide « {} » : {}", pattern, e))?);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Charge les `.gitignore` du projet, en ignorant les dossiers qu'ils excluent eux-mêmes.
+fn find_gitignores(root: &Path) -> Vec<Gitignore> {
+    WalkBuilder::new(root)
+        .hidden(false)
+        .build()
+        .flatten()
+        .filter(|entry| entry.file_name() == ".gitignore")
+        .filter_map(|entry| {
+            let (gitignore, error) = Gitignore::new(entry.path());
+            if let Some(e) = error {
+                warn!("Erreur de lecture de {} : {}", entry.path().display(), e);
+            }
+            (!gitignore.is_empty()).then_some(gitignore)
+        })
+        .collect()
+}
+
+/// Dossiers à surveiller sous une base : elle-même et ses sous-dossiers non ignorés par les
+/// `.gitignore`, chacun surveillé sans récursion. Une surveillance récursive de la racine
+/// inclurait `node_modules`, `target`, ... et épuiserait les watches inotify.
+fn watch_dirs(base: &Path) -> Vec<PathBuf> {
+    WalkBuilder::new(base)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build()
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|file_type| file_type.is_dir()))
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+/// Dossier à surveiller pour un motif : sa partie fixe (`src` pour `src/**/*.ts`),
+/// ou le dossier existant le plus proche.
+fn glob_base(root: &Path, pattern: &str) -> PathBuf {
+    let components: Vec<&str> = pattern.split('/').collect();
+    let fixed = components
+        .iter()
+        .position(|component| component.contains(['*', '?', '[', '{']))
+        .unwrap_or(components.len().saturating_sub(1));
+
+    let mut base = components[..fixed].iter().fold(root.to_path_buf(), |base, component| base.join(component));
+    while !base.is_dir() && base != root {
+        base.pop();
+    }
+    base
+}
+
+/// Surveille les fichiers des projets pour relancer les scripts dotés d'un déclencheur.
+#[derive(Default)]
+pub struct WatchTriggers {
+    watcher: Arc<Mutex<Option<RecommendedWatcher>>>,
+    watched: Arc<Mutex<Vec<PathBuf>>>,
+    triggers: Arc<Mutex<Vec<TriggerSpec>>>,
+}
+
+impl WatchTriggers {
+    /// Démarre la surveillance pour les déclencheurs des projets fournis.
+    pub fn start<R: Runtime>(&self, app: AppHandle<R>, projects: &[ProjectConfig]) {
+        let (tx, rx) = mpsc::channel();
+        let watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                error!("Impossible de démarrer la surveillance des déclencheurs : {}", e);
+                return;
+            }
+        };
+
+        *self.watcher.lock().unwrap_or_else(PoisonError::into_inner) = Some(watcher);
+        let watching = Watching {
+            watcher: Arc::clone(&self.watcher),
+            watched: Arc::clone(&self.watched),
+            triggers: Arc::clone(&self.triggers),
+        };
+        thread::spawn(move || trigger_loop(app, rx, watching));
+        self.reload(projects);
+    }
+
+    /// Recalcule les déclencheurs et les dossiers surveillés après une modification de la configuration.
+    pub fn reload(&self, projects: &[ProjectConfig]) {
+        let mut triggers = Vec::new();
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for project in projects {
+            for (key, settings) in &project.script_settings {
+                let Some(trigger) = settings.watch.as_ref() else {
+                    continue;
+                };
+                match TriggerSpec::build(project, key, trigger) {
+                    Ok(spec) => {
+                        dirs.extend(spec.bases(&trigger.include).iter().flat_map(|base| watch_dirs(base)));
+                        triggers.push(spec);
+                    }
+                    Err(e) => warn!("Déclencheur ignoré pour le script '{}' du projet {} : {}", key, project.name, e),
+                }
+            }
+        }
+
+        dirs.sort();
+        dirs.dedup();
+
+        if let Some(watcher) = self.watcher.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+            let mut watched = self.watched.lock().unwrap_or_else(PoisonError::into_inner);
+            for dir in watched.iter().filter(|dir| !dirs.contains(dir)) {
+                let _ = watcher.unwatch(dir);
+            }
+            for dir in dirs.iter().filter(|dir| !watched.contains(dir)) {
+                if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                    warn!("Impossible de surveiller {} : {}", dir.display(), e);
+                }
+            }
+            *watched = dirs;
+        }
+        *self.triggers.lock().unwrap_or_else(PoisonError::into_inner) = triggers;
+    }
+}
+
+/// Surveillance partagée avec la boucle des déclencheurs, pour y ajouter les dossiers créés.
+struct Watching {
+    watcher: Arc<Mutex<Option<RecommendedWatcher>>>,
+    watched: Arc<Mutex<Vec<PathBuf>>>,
+    triggers: Arc<Mutex<Vec<TriggerSpec>>>,
+}
+
+impl Watching {
+    /// Surveille un dossier créé dans un projet, et ses sous-dossiers non ignorés.
+    fn watch_created_dir(&self, dir: &Path) {
+        let wanted = self
+            .triggers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .any(|trigger| trigger.watches_dir(dir));
+        if !wanted {
+            return;
+        }
+        if let Some(watcher) = self.watcher.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+            let mut watched = self.watched.lock().unwrap_or_else(PoisonError::into_inner);
+            for dir in watch_dirs(dir) {
+                if watched.contains(&dir) {
+                    continue;
+                }
+                match watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                    Ok(()) => watched.push(dir),
+                    Err(e) => warn!("Impossible de surveiller {} : {}", dir.display(), e),
+                }
+            }
+        }
+    }
+}
+
+/// Changement en attente de la fin du délai de regroupement de son déclencheur.
+struct PendingChange {
+    last_event: Instant,
+    debounce: Duration,
+    action: WatchAction,
+    path: PathBuf,
+}
+
+/// Regroupe les changements de chaque déclencheur et relance le script une fois le délai écoulé.
+fn trigger_loop<R: Runtime>(
+    app: AppHandle<R>,
+    rx: Receiver<notify::Result<Event>>,
+    watching: Watching,
+) {
+    let mut pending: HashMap<(String, String), PendingChange> = HashMap::new();
+    loop {
+        let next_due = pending
+            .values()
+            .map(|change| (change.last_event + change.debounce).saturating_duration_since(Instant::now()))
+            .min();
+        let received = match next_due {
+            Some(timeout) => rx.recv_timeout(timeout),
+            None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match received {
+            Ok(Ok(event)) if !matches!(event.kind, EventKind::Access(_)) => {
+                if matches!(event.kind, EventKind::Create(_)) {
+                    for dir in event.paths.iter().filter(|path| path.is_dir()) {
+                        watching.watch_created_dir(dir);
+                    }
+                }
+                let triggers = watching.triggers.lock().unwrap_or_else(PoisonError::into_inner);
+                for path in &event.paths {
+                    for trigger in triggers.iter().filter(|trigger| trigger.matches(path)) {
+                        pending.insert(
+                            (trigger.project_id.clone(), trigger.key.clone()),
+                            PendingChange {
+                                last_event: Instant::now(),
+                                debounce: trigger.debounce,
+                                action: trigger.action,
+                                path: path.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+            Ok(Ok(_)) | Err(RecvTimeoutError::Timeout) => {}
+            Ok(Err(e)) => warn!("Erreur de surveillance des fichiers : {}", e),
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        let now = Instant::now();
+        let due: Vec<(String, String)> = pending
+            .iter()
+            .filter(|(_, change)| now >= change.last_event + change.debounce)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for (project_id, key) in due {
+            if let Some(change) = pending.remove(&(project_id.clone(), key.clone())) {
+                fire(&app, project_id, &key, change);
+            }
+        }
+    }
+}
+
+/// Redémarre ou relance un script suite à un changement de fichier (`watch_triggered`).
+fn fire<R: Runtime>(app: &AppHandle<R>, project_id: String, key: &str, change: PendingChange) {
+    let state = app.state::<AppState>();
+    let Some(project) = state.store.project(&project_id) else {
+        return;
+    };
+    let (source, script) = split_script_settings_key(key);
+    let running = state
+        .store
+        .processes()
+        .into_iter()
+        .find(|process| process.runs(&project_id, source, script));
+    if running.is_none() && change.action == WatchAction::Restart {
+        return;
+    }
+
+    info!(
+        "Changement de {} : relance du script '{}' du projet {}",
+        change.path.display(),
+        script,
+        project.name
+    );
+    match running {
+        Some(process) => {
+            if let Err(e) = request_restart(app, &process.run_id) {
+                warn!("Impossible de redémarrer le script '{}' : {}", script, e);
+                return;
+            }
+        }
+        None => {
+            let app = app.clone();
+            let (source, script) = (source.map(str::to_string), script.to_string());
+            tauri::async_runtime::spawn(async move {
+                let path = PathBuf::from(&project.path);
+                let Some(task) = find_task(&path, source.as_deref(), &script) else {
+                    warn!("Tâche {} introuvable pour {}.", script, project.name);
+                    return;
+                };
+                let request = LaunchRequest {
+                    trigger: RunTrigger::Watch,
+                    ..LaunchRequest::for_task(&project.id, task, path)
+                };
+                if let Err(e) = submit(app, request).await {
+                    error!("Échec du lancement du script '{}' : {}", script, e);
+                }
+            });
+        }
+    }
+
+    let payload = json!({
+        "id": project_id,
+        "script": script,
+        "source": source,
+        "action": change.action,
+        "path": change.path,
+    });
+    if let Err(e) = app.emit("watch_triggered", payload) {
+        error!("Erreur lors de l'émission de watch_triggered: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Projet temporaire contenant les fichiers donnés (les chemins finissant par `/` sont des dossiers).
+    fn project_with(files: &[(&str, &str)]) -> (tempfile::TempDir, ProjectConfig) {
+        let dir = tempfile::tempdir().unwrap();
+        for (file, contents) in files {
+            let path = dir.path().join(file);
+            if file.ends_with('/') {
+                fs::create_dir_all(&path).unwrap();
+            } else {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, contents).unwrap();
+            }
+        }
+        let project = ProjectConfig {
+            id: "project".to_string(),
+            path: dir.path().to_string_lossy().to_string(),
+            name: "project".to_string(),
+            framework: String::new(),
+            framework_url: None,
+            git_remote: None,
+            package_name: None,
+            framework_version: None,
+            script_settings: HashMap::new(),
+            schedules: Vec::new(),
+        };
+        (dir, project)
+    }
+
+    fn trigger(include: &[&str], exclude: &[&str]) -> WatchTrigger {
+        WatchTrigger {
+            include: include.iter().map(|pattern| pattern.to_string()).collect(),
+            exclude: exclude.iter().map(|pattern| pattern.to_string()).collect(),
+            debounce_ms: default_debounce_ms(),
+            action: WatchAction::Restart,
+        }
+    }
+
+    #[test]
+    fn finds_glob_base() {
+        let (dir, _) = project_with(&[("src/components/", "")]);
+        let root = dir.path();
+        assert_eq!(glob_base(root, "src/**/*.ts"), root.join("src"));
+        assert_eq!(glob_base(root, "src/components/*.vue"), root.join("src/components"));
+        assert_eq!(glob_base(root, "src/components/Button.vue"), root.join("src/components"));
+        // Dossier inexistant : le plus proche parent existant.
+        assert_eq!(glob_base(root, "src/pages/**"), root.join("src"));
+        assert_eq!(glob_base(root, "**/*.rs"), root);
+        assert_eq!(glob_base(root, "package.json"), root);
+    }
+
+    #[test]
+    fn matches_included_files_only() {
+        let (dir, project) = project_with(&[(".gitignore", "dist/\n*.log\n")]);
+        let root = dir.path();
+        let spec = TriggerSpec::build(&project, "package.json::dev", &trigger(&["src/**"], &["src/**/*.test.ts"])).unwrap();
+
+        assert!(spec.matches(&root.join("src/main.ts")));
+        assert!(spec.matches(&root.join("src/lib/util.ts")));
+        assert!(!spec.matches(&root.join("src/lib/util.test.ts")));
+        assert!(!spec.matches(&root.join("README.md")));
+        assert!(!spec.matches(&root.join("src/debug.log")));
+        assert!(!spec.matches(Path::new("/elsewhere/src/main.ts")));
+    }
+
+    #[test]
+    fn matches_whole_project_except_ignored_files() {
+        let (dir, project) = project_with(&[(".gitignore", "dist/\nnode_modules/\n"), ("dist/", "")]);
+        let root = dir.path();
+        let spec = TriggerSpec::build(&project, "dev", &trigger(&[], &[])).unwrap();
+
+        assert!(spec.matches(&root.join("index.js")));
+        assert!(spec.matches(&root.join("src/app.js")));
+        assert!(!spec.matches(&root.join("dist/bundle.js")));
+        assert!(!spec.matches(&root.join("node_modules/vite/index.js")));
+        assert!(!spec.matches(&root.join(".git/index")));
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let (_dir, project) = project_with(&[]);
+        assert!(TriggerSpec::build(&project, "dev", &trigger(&["src/[a"], &[])).is_err());
+    }
+
+    #[test]
+    fn watches_each_non_ignored_dir() {
+        let (dir, project) = project_with(&[
+            (".gitignore", "node_modules/\n"),
+            ("src/lib/", ""),
+            ("node_modules/vite/dist/", ""),
+            (".git/objects/", ""),
+        ]);
+        let root = dir.path();
+        let mut dirs = watch_dirs(root);
+        dirs.sort();
+        assert_eq!(dirs, [root.to_path_buf(), root.join("src"), root.join("src/lib")]);
+
+        let spec = TriggerSpec::build(&project, "dev", &trigger(&[], &[])).unwrap();
+        assert!(spec.watches_dir(&root.join("src/new")));
+        assert!(!spec.watches_dir(&root.join("node_modules/react")));
+        assert!(!spec.watches_dir(&root.join(".git/refs")));
+    }
+}
@@ -1,8 +1,25 @@
 // src-tauri/src/types.rs
 
+use crate::framework::FrameworkMatch;
+use crate::history::RunHistory;
+use crate::limits::ResourceLimits;
+use crate::metrics::MetricsHistory;
+use crate::package_json::PackageJsonCache;
+use crate::probes::{HealthCheck, ScriptHealth};
+use crate::project_type::ProjectType;
+use crate::queue::RunQueue;
+use crate::readiness::ReadinessCheck;
+use crate::runner::{RestartPolicy, RunEvent, RunTrigger};
+use crate::scheduler::Schedule;
+use crate::store::StateStore;
+use crate::toolchain::ToolchainCache;
+use crate::triggers::{WatchTrigger, WatchTriggers};
+use crate::versions::PackageVersion;
+use crate::watcher::ProjectWatcher;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::async_runtime::Mutex;
+use std::sync::Arc;
+use tokio::sync::broadcast;
 
 /// Structure représentant un projet.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,10 +30,15 @@ pub struct Project {
     pub framework: Option<String>,
     pub framework_url: Option<String>,
     pub description: Option<String>,
-    pub status: Option<String>,
+    pub status: Option<ProjectStatus>,
     #[serde(rename = "packageManager")]
     pub package_manager: Option<String>,
     pub scripts: Option<HashMap<String, String>>,
+    pub health: Option<ProjectHealth>,
+    pub frameworks: Option<Vec<FrameworkMatch>>,
+    pub framework_version: Option<PackageVersion>,
+    pub project_types: Option<Vec<ProjectType>>,
+    pub tasks: Option<Vec<Task>>,
 }
 
 // Structure représentant la configuration d'un projet.
@@ -27,12 +49,181 @@ pub struct ProjectConfig {
     pub name: String,
     pub framework: String,
     pub framework_url: Option<String>,
+    #[serde(default)]
+    pub git_remote: Option<String>,
+    #[serde(default)]
+    pub package_name: Option<String>,
+    #[serde(default)]
+    pub framework_version: Option<PackageVersion>,
+    /// Réglages des scripts, indexés par `script_settings_key` (source et nom de la tâche).
+    #[serde(default)]
+    pub script_settings: HashMap<String, ScriptSettings>,
+    #[serde(default)]
+    pub schedules: Vec<Schedule>,
 }
 
+impl ProjectConfig {
+    /// Réglages d'un script identifié par sa source et son nom ; à défaut, ceux enregistrés
+    /// sous son seul nom, sans source précisée.
+    pub fn settings_for(&self, source: Option<&str>, script: &str) -> Option<&ScriptSettings> {
+        self.script_settings
+            .get(&script_settings_key(source, script))
+            .or_else(|| self.script_settings.get(script))
+    }
+}
+
+/// Séparateur entre la source et le nom d'une tâche dans les clés de `ProjectConfig::script_settings`.
+const SCRIPT_KEY_SEPARATOR: &str = "::";
+
+/// Clé des réglages d'un script : `package.json::dev`, ou le seul nom sans source précisée.
+pub fn script_settings_key(source: Option<&str>, script: &str) -> String {
+    match source {
+        Some(source) => format!("{}{}{}", source, SCRIPT_KEY_SEPARATOR, script),
+        None => script.to_string(),
+    }
+}
+
+/// Décompose une clé de réglages en source (si précisée) et nom de script.
+pub fn split_script_settings_key(key: &str) -> (Option<&str>, &str) {
+    match key.split_once(SCRIPT_KEY_SEPARATOR) {
+        Some((source, script)) => (Some(source), script),
+        None => (None, key),
+    }
+}
+
+/// Script d'un projet, éventuellement d'un autre projet que celui qui le référence.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ScriptRef {
+    #[serde(default)]
+    pub project_id: Option<String>,
+    pub script: String,
+    /// Source de la tâche (`package.json`, `Makefile`, ...) ; la première tâche du même nom sinon.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// Réglages d'exécution d'un script, indexés par source et nom de script dans `ProjectConfig`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScriptSettings {
+    #[serde(default)]
+    pub depends_on: Vec<ScriptRef>,
+    #[serde(default)]
+    pub readiness: Option<ReadinessCheck>,
+    #[serde(default)]
+    pub readiness_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    #[serde(default)]
+    pub health_check: Option<HealthCheck>,
+    #[serde(default)]
+    pub limits: ResourceLimits,
+    /// Étiquettes soumises aux limites de concurrence (`build`, `test`, ...).
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub watch: Option<WatchTrigger>,
+}
+
+/// État de santé d'un projet, calculé à chaque récupération.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectHealth {
+    Ok,
+    MissingDirectory,
+    /// Anciennement `missing_package_json`, toujours accepté à la lecture.
+    #[serde(alias = "missing_package_json")]
+    MissingManifest,
+    InvalidPackageJson,
+}
+
+/// Configuration d'un projet accompagnée de son état de santé.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectEntry {
+    #[serde(flatten)]
+    pub config: ProjectConfig,
+    pub health: ProjectHealth,
+}
+
+/// Statut d'exécution d'un projet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectStatus {
+    Idle,
+    Running,
+    Unhealthy,
+}
+
+/// Structure représentant un script en cours d'exécution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningProcess {
+    pub run_id: String,
+    pub project_id: String,
+    pub script: String,
+    /// Source de la tâche lancée (`package.json`, `Makefile`, ...), inconnue pour une commande libre.
+    #[serde(default)]
+    pub source: Option<String>,
+    pub manager: String,
+    pub pid: Option<u32>,
+    pub started_at: String,
+    #[serde(default)]
+    pub trigger: RunTrigger,
+    #[serde(default)]
+    pub restarts: u32,
+    #[serde(default)]
+    pub stop_requested: bool,
+    #[serde(default)]
+    pub restart_requested: bool,
+    #[serde(default)]
+    pub health: Option<ScriptHealth>,
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Processus lancé par une instance précédente de l'application : sa sortie n'est plus relayée
+    /// et il ne peut pas être redémarré.
+    #[serde(default)]
+    pub adopted: bool,
+}
+
+impl RunningProcess {
+    /// Indique si le processus exécute le script donné ; une source inconnue d'un côté ou de l'autre
+    /// n'empêche pas la correspondance.
+    pub fn runs(&self, project_id: &str, source: Option<&str>, script: &str) -> bool {
+        self.project_id == project_id
+            && self.script == script
+            && (source.is_none() || self.source.is_none() || self.source.as_deref() == source)
+    }
+}
+
+/// Nombre d'événements d'exécution conservés pour les abonnés en retard.
+const RUN_EVENTS_CAPACITY: usize = 1024;
+
 /// Structure représentant l'état global de l'application.
-#[derive(Default)]
 pub struct AppState {
-    pub projects: Mutex<Vec<Project>>,
+    pub store: StateStore,
+    pub package_cache: Arc<PackageJsonCache>,
+    pub watcher: ProjectWatcher,
+    pub watch_triggers: WatchTriggers,
+    pub toolchain: Arc<ToolchainCache>,
+    pub run_events: broadcast::Sender<RunEvent>,
+    pub metrics: MetricsHistory,
+    pub run_queue: RunQueue,
+    pub history: RunHistory,
+}
+
+impl AppState {
+    /// Charge la configuration une seule fois et construit l'état de l'application.
+    pub fn load() -> Self {
+        AppState {
+            store: StateStore::load(),
+            package_cache: Arc::new(PackageJsonCache::default()),
+            watcher: ProjectWatcher::default(),
+            watch_triggers: WatchTriggers::default(),
+            toolchain: Arc::new(ToolchainCache::default()),
+            run_events: broadcast::channel(RUN_EVENTS_CAPACITY).0,
+            metrics: MetricsHistory::default(),
+            run_queue: RunQueue::default(),
+            history: RunHistory::default(),
+        }
+    }
 }
 
 /// Structure pour FetchPackageJson.
@@ -40,4 +231,32 @@ pub struct AppState {
 pub struct FetchPackageJson {
     pub manager: String,
     pub scripts: HashMap<String, String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub reason: DetectionReason,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Tâche exécutable d'un projet, quelle que soit sa source (package.json, Cargo.toml, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    pub name: String,
+    pub source: String,
+    pub command: String,
+    pub program: String,
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Raison ayant conduit au choix du gestionnaire de paquets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionReason {
+    PackageManagerField,
+    Lockfile,
+    #[default]
+    Default,
 }
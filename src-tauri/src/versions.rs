@@ -0,0 +1,362 @@
+// src-tauri/src/versions.rs
+
+use crate::package_json::read_package_json;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Version d'un paquet : plage déclarée dans package.json et version réellement résolue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageVersion {
+    pub package: String,
+    pub declared: Option<String>,
+    pub resolved: Option<String>,
+    pub source: Option<String>,
+}
+
+/// Fonction de résolution d'une version à partir d'une source (fichier de lock ou node_modules).
+type Resolver = fn(&Path, &str, Option<&str>) -> Option<String>;
+
+/// Détermine la version déclarée et la version résolue d'un paquet dans un projet.
+pub fn detect_package_version(project_dir: &Path, package_json: &Value, package: &str) -> PackageVersion {
+    let declared = ["dependencies", "devDependencies", "peerDependencies"]
+        .iter()
+        .find_map(|field| package_json.get(field)?.get(package)?.as_str())
+        .map(str::to_string);
+
+    let resolvers: [(&str, Resolver); 4] = [
+        ("package-lock.json", from_package_lock),
+        ("pnpm-lock.yaml", from_pnpm_lock),
+        ("yarn.lock", from_yarn_lock),
+        ("node_modules", from_node_modules),
+    ];
+    let resolved = resolvers
+        .iter()
+        .find_map(|(source, resolver)| {
+            resolver(project_dir, package, declared.as_deref()).map(|version| (version, *source))
+        });
+
+    PackageVersion {
+        package: package.to_string(),
+        declared,
+        source: resolved.as_ref().map(|(_, source)| source.to_string()),
+        resolved: resolved.map(|(version, _)| version),
+    }
+}
+
+/// Lit la version installée dans `node_modules/<paquet>/package.json`.
+fn from_node_modules(project_dir: &Path, package: &str, _declared: Option<&str>) -> Option<String> {
+    read_package_json(&project_dir.join("node_modules").join(package))?
+        .get("version")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Lit la version dans package-lock.json (format v2/v3 `packages`, puis v1 `dependencies`).
+fn from_package_lock(project_dir: &Path, package: &str, _declared: Option<&str>) -> Option<String> {
+    let contents = fs::read_to_string(project_dir.join("package-lock.json")).ok()?;
+    let lock: Value = serde_json::from_str(&contents).ok()?;
+
+    lock.get("packages")
+        .and_then(|packages| packages.get(format!("node_modules/{}", package)))
+        .or_else(|| lock.get("dependencies").and_then(|deps| deps.get(package)))
+        .and_then(|entry| entry.get("version"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Sections de pnpm-lock.yaml listant les dépendances directes d'un projet. `specifiers:`
+/// (plages déclarées, format 5.x) en est exclue.
+const PNPM_DEPENDENCY_SECTIONS: &[&str] = &["dependencies", "devDependencies", "optionalDependencies"];
+
+/// Lit la version dans pnpm-lock.yaml, parmi les dépendances directes du projet racine :
+/// sections de premier niveau (formats 5.x et 6) ou de l'importeur `.` (formats 5.4+ et 9).
+fn from_pnpm_lock(project_dir: &Path, package: &str, _declared: Option<&str>) -> Option<String> {
+    let contents = fs::read_to_string(project_dir.join("pnpm-lock.yaml")).ok()?;
+
+    // Clés englobantes de la ligne courante, avec leur indentation.
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    let mut lines = contents.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+        while parents.last().is_some_and(|(parent_indent, _)| *parent_indent >= indent) {
+            parents.pop();
+        }
+        let Some((key, value)) = split_yaml_entry(trimmed) else {
+            continue;
+        };
+
+        let section: Vec<&str> = parents.iter().map(|(_, key)| key.as_str()).collect();
+        let is_root_dependency = match section.as_slice() {
+            [section] | ["importers", ".", section] => PNPM_DEPENDENCY_SECTIONS.contains(section),
+            _ => false,
+        };
+        if !is_root_dependency || key != package {
+            parents.push((indent, key));
+            continue;
+        }
+
+        // Format 5.x : `next: 12.3.1_react@18.2.0`
+        if !value.is_empty() {
+            return Some(clean_pnpm_version(value));
+        }
+
+        // Formats 6 et 9 : bloc `specifier:` / `version:` indenté sous le nom du paquet
+        for nested in lines.by_ref() {
+            let nested_trimmed = nested.trim_start();
+            if nested.len() - nested_trimmed.len() <= indent {
+                break;
+            }
+            if let Some(version) = nested_trimmed.strip_prefix("version:") {
+                return Some(clean_pnpm_version(version.trim()));
+            }
+        }
+        return None;
+    }
+
+    None
+}
+
+/// Sépare une ligne YAML `clé: valeur` (clé éventuellement entre guillemets).
+fn split_yaml_entry(line: &str) -> Option<(String, &str)> {
+    let (key, rest) = match line.chars().next()? {
+        quote @ ('\'' | '"') => {
+            let end = line[1..].find(quote)? + 1;
+            (&line[1..end], &line[end + 1..])
+        }
+        _ => {
+            let end = line.find(':')?;
+            (&line[..end], &line[end..])
+        }
+    };
+    let value = rest.strip_prefix(':')?.trim();
+    Some((key.to_string(), value))
+}
+
+/// Retire les suffixes de pairs (`(react@18.2.0)` ou `_react@18.2.0`) d'une version pnpm.
+fn clean_pnpm_version(version: &str) -> String {
+    version
+        .trim_matches(|c| c == '\'' || c == '"')
+        .split(['(', '_'])
+        .next()
+        .unwrap_or(version)
+        .to_string()
+}
+
+/// Lit la version dans yarn.lock (formats classique et Berry), en privilégiant
+/// l'entrée correspondant à la plage déclarée lorsque plusieurs versions coexistent.
+fn from_yarn_lock(project_dir: &Path, package: &str, declared: Option<&str>) -> Option<String> {
+    let contents = fs::read_to_string(project_dir.join("yarn.lock")).ok()?;
+    let prefix = format!("{}@", package);
+    let declared_specs: Vec<String> = declared
+        .map(|range| vec![format!("{}{}", prefix, range), format!("{}npm:{}", prefix, range)])
+        .unwrap_or_default();
+
+    let mut fallback = None;
+    let mut lines = contents.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with(' ') || !line.ends_with(':') {
+            continue;
+        }
+
+        let specs: Vec<&str> = line
+            .trim_end_matches(':')
+            .split(',')
+            .map(|spec| spec.trim().trim_matches('"'))
+            .filter(|spec| spec.starts_with(&prefix))
+            .collect();
+        if specs.is_empty() {
+            continue;
+        }
+
+        let Some(version) = read_yarn_entry_version(&mut lines) else {
+            continue;
+        };
+        if specs.iter().any(|spec| declared_specs.iter().any(|declared| declared == spec)) {
+            return Some(version);
+        }
+        fallback.get_or_insert(version);
+    }
+
+    fallback
+}
+
+/// Lit le champ `version` d'une entrée de yarn.lock.
+fn read_yarn_entry_version<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Option<String> {
+    for entry_line in lines {
+        if !entry_line.starts_with(' ') {
+            break;
+        }
+        let entry_line = entry_line.trim();
+        if let Some(version) = entry_line
+            .strip_prefix("version:")
+            .or_else(|| entry_line.strip_prefix("version "))
+        {
+            return Some(version.trim().trim_matches('"').to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn project_with(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, contents) in files {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    fn resolve(files: &[(&str, &str)]) -> PackageVersion {
+        let dir = project_with(files);
+        let package_json = json!({ "dependencies": { "next": "^13.4.0" } });
+        detect_package_version(dir.path(), &package_json, "next")
+    }
+
+    #[test]
+    fn reads_package_lock_v3() {
+        let version = resolve(&[(
+            "package-lock.json",
+            r#"{ "lockfileVersion": 3, "packages": { "": {}, "node_modules/next": { "version": "13.4.19" } } }"#,
+        )]);
+        assert_eq!(version.declared.as_deref(), Some("^13.4.0"));
+        assert_eq!(version.resolved.as_deref(), Some("13.4.19"));
+        assert_eq!(version.source.as_deref(), Some("package-lock.json"));
+    }
+
+    #[test]
+    fn reads_package_lock_v1() {
+        let version = resolve(&[(
+            "package-lock.json",
+            r#"{ "lockfileVersion": 1, "dependencies": { "next": { "version": "13.4.12" } } }"#,
+        )]);
+        assert_eq!(version.resolved.as_deref(), Some("13.4.12"));
+    }
+
+    #[test]
+    fn reads_pnpm_lock_v5_dependencies_not_specifiers() {
+        let lock = "\
+lockfileVersion: 5.4
+
+specifiers:
+  next: ^13.4.0
+  react: ^18.2.0
+
+dependencies:
+  next: 13.4.19_react@18.2.0
+  react: 18.2.0
+
+packages:
+
+  /next/13.4.19_react@18.2.0:
+    resolution: {integrity: sha512-xxx}
+";
+        let version = resolve(&[("pnpm-lock.yaml", lock)]);
+        assert_eq!(version.resolved.as_deref(), Some("13.4.19"));
+        assert_eq!(version.source.as_deref(), Some("pnpm-lock.yaml"));
+    }
+
+    #[test]
+    fn reads_pnpm_lock_v6() {
+        let lock = "\
+lockfileVersion: '6.0'
+
+dependencies:
+  next:
+    specifier: ^13.4.0
+    version: 13.4.19(react-dom@18.2.0)(react@18.2.0)
+
+packages:
+
+  /next@13.4.19(react-dom@18.2.0)(react@18.2.0):
+    resolution: {integrity: sha512-xxx}
+";
+        let version = resolve(&[("pnpm-lock.yaml", lock)]);
+        assert_eq!(version.resolved.as_deref(), Some("13.4.19"));
+    }
+
+    #[test]
+    fn reads_pnpm_lock_v9_root_importer() {
+        let lock = "\
+lockfileVersion: '9.0'
+
+importers:
+
+  packages/docs:
+    dependencies:
+      next:
+        specifier: ^12.0.0
+        version: 12.3.4
+
+  .:
+    specifiers:
+      next: ^13.4.0
+    devDependencies:
+      next:
+        specifier: ^13.4.0
+        version: 13.5.6(react@18.2.0)
+
+packages:
+
+  next@13.5.6:
+    resolution: {integrity: sha512-xxx}
+";
+        let version = resolve(&[("pnpm-lock.yaml", lock)]);
+        assert_eq!(version.resolved.as_deref(), Some("13.5.6"));
+    }
+
+    #[test]
+    fn reads_yarn_classic_entry_matching_range() {
+        let lock = r#"# yarn lockfile v1
+
+next@^12.0.0:
+  version "12.3.4"
+
+next@^13.4.0:
+  version "13.4.19"
+  resolved "https://registry.yarnpkg.com/next/-/next-13.4.19.tgz"
+"#;
+        let version = resolve(&[("yarn.lock", lock)]);
+        assert_eq!(version.resolved.as_deref(), Some("13.4.19"));
+        assert_eq!(version.source.as_deref(), Some("yarn.lock"));
+    }
+
+    #[test]
+    fn reads_yarn_berry_entry() {
+        let lock = r#"__metadata:
+  version: 6
+
+"next@npm:^13.4.0":
+  version: 13.4.19
+  resolution: "next@npm:13.4.19"
+"#;
+        let version = resolve(&[("yarn.lock", lock)]);
+        assert_eq!(version.resolved.as_deref(), Some("13.4.19"));
+    }
+
+    #[test]
+    fn falls_back_to_node_modules() {
+        let version = resolve(&[("node_modules/next/package.json", r#"{ "name": "next", "version": "13.4.7" }"#)]);
+        assert_eq!(version.resolved.as_deref(), Some("13.4.7"));
+        assert_eq!(version.source.as_deref(), Some("node_modules"));
+    }
+
+    #[test]
+    fn unresolved_without_lockfile_or_node_modules() {
+        let version = resolve(&[]);
+        assert_eq!(version.declared.as_deref(), Some("^13.4.0"));
+        assert!(version.resolved.is_none());
+        assert!(version.source.is_none());
+    }
+}
@@ -0,0 +1,169 @@
+// src-tauri/src/watcher.rs
+
+use crate::framework::fetch_framework;
+use crate::health::check_project_health;
+use crate::project_type::manifest_file_names;
+use crate::package_json::read_package_json;
+use crate::script::{detect_from_package_json, detect_tasks, PACKAGE_MANAGERS_AND_LOCKFILES, TASK_FILES};
+use crate::types::{AppState, ProjectConfig};
+use log::{error, info, warn};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::json;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Délai pendant lequel les événements d'un même projet sont regroupés.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Surveille les manifestes et les fichiers de lock de chaque projet enregistré.
+#[derive(Default)]
+pub struct ProjectWatcher {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+impl ProjectWatcher {
+    /// Démarre la surveillance des projets fournis.
+    pub fn start<R: Runtime>(&self, app: AppHandle<R>, projects: &[ProjectConfig]) {
+        let (tx, rx) = mpsc::channel();
+        let watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                error!("Impossible de démarrer la surveillance des projets : {}", e);
+                return;
+            }
+        };
+
+        *self.watcher.lock().unwrap_or_else(PoisonError::into_inner) = Some(watcher);
+        thread::spawn(move || watch_loop(app, rx));
+
+        for project in projects {
+            self.watch(Path::new(&project.path));
+        }
+    }
+
+    /// Ajoute le dossier d'un projet à la surveillance.
+    pub fn watch(&self, project_dir: &Path) {
+        if let Some(watcher) = self
+            .watcher
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+        {
+            // Le dossier est surveillé sans récursion pour détecter l'apparition d'un nouveau fichier de lock.
+            if let Err(e) = watcher.watch(project_dir, RecursiveMode::NonRecursive) {
+                warn!("Impossible de surveiller {} : {}", project_dir.display(), e);
+            }
+        }
+    }
+
+    /// Retire le dossier d'un projet de la surveillance.
+    pub fn unwatch(&self, project_dir: &Path) {
+        if let Some(watcher) = self
+            .watcher
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+        {
+            let _ = watcher.unwatch(project_dir);
+        }
+    }
+}
+
+/// Indique si un fichier modifié doit déclencher une mise à jour du projet.
+fn is_watched_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| {
+            manifest_file_names().any(|manifest| manifest == name)
+                || TASK_FILES.contains(&name)
+                || PACKAGE_MANAGERS_AND_LOCKFILES
+                    .iter()
+                    .any(|(_, lockfile)| *lockfile == name)
+        })
+        .unwrap_or(false)
+}
+
+/// Regroupe les événements reçus et met à jour les projets concernés.
+fn watch_loop<R: Runtime>(app: AppHandle<R>, rx: Receiver<notify::Result<Event>>) {
+    while let Ok(first) = rx.recv() {
+        let mut changed_dirs = HashSet::new();
+        collect_changed_dirs(first, &mut changed_dirs);
+        while let Ok(next) = rx.recv_timeout(WATCH_DEBOUNCE) {
+            collect_changed_dirs(next, &mut changed_dirs);
+        }
+
+        for project_dir in changed_dirs {
+            refresh_project(&app, &project_dir);
+        }
+    }
+}
+
+/// Extrait les dossiers de projet touchés par un événement.
+fn collect_changed_dirs(event: notify::Result<Event>, changed_dirs: &mut HashSet<PathBuf>) {
+    match event {
+        Ok(event) => changed_dirs.extend(
+            event
+                .paths
+                .iter()
+                .filter(|path| is_watched_file(path))
+                .filter_map(|path| path.parent().map(Path::to_path_buf)),
+        ),
+        Err(e) => warn!("Erreur de surveillance des fichiers : {}", e),
+    }
+}
+
+/// Recalcule les scripts, les tâches, le gestionnaire et le framework d'un projet puis émet `project_updated`.
+fn refresh_project<R: Runtime>(app: &AppHandle<R>, project_dir: &Path) {
+    let state = app.state::<AppState>();
+    let project_dir_str = project_dir.to_string_lossy().to_string();
+    let Some(project) = state
+        .store
+        .projects()
+        .into_iter()
+        .find(|project| project.path == project_dir_str)
+    else {
+        return;
+    };
+
+    let package_json = read_package_json(project_dir);
+    let package_info = package_json
+        .as_ref()
+        .map(|package_json| detect_from_package_json(project_dir, package_json));
+    let framework_data = fetch_framework(project_dir);
+
+    if let Some(framework) = framework_data.as_ref() {
+        let resolved = framework.version.as_ref().and_then(|v| v.resolved.as_ref());
+        let stored_resolved = project.framework_version.as_ref().and_then(|v| v.resolved.as_ref());
+        if framework.name != project.framework || resolved != stored_resolved {
+            if let Err(e) = state.store.update(|config| {
+                if let Some(stored) = config.project_folders.iter_mut().find(|p| p.id == project.id) {
+                    stored.framework = framework.name.clone();
+                    stored.framework_url = Some(framework.url.clone());
+                    stored.framework_version = framework.version.clone();
+                }
+            }) {
+                error!("Erreur lors de la mise à jour de la configuration : {}", e);
+            }
+        }
+    }
+
+    info!("Projet avec ID {} mis à jour après une modification de fichier.", project.id);
+    let payload = json!({
+        "id": project.id,
+        "health": check_project_health(project_dir),
+        "manager": package_info.as_ref().map(|info| info.manager.clone()),
+        "scripts": package_info.map(|info| info.scripts),
+        "framework": framework_data.as_ref().map(|f| f.name.clone()),
+        "framework_url": framework_data.as_ref().map(|f| f.url.clone()),
+        "framework_version": framework_data.and_then(|f| f.version),
+        "tasks": detect_tasks(project_dir, package_json.as_ref()),
+    });
+    if let Err(e) = app.emit("project_updated", payload) {
+        error!("Erreur lors de l'émission de project_updated: {}", e);
+    }
+}